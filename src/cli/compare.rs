@@ -0,0 +1,75 @@
+use crate::processing::comparison::{self, DiffEntry};
+use crate::processing::AnalyisisResult;
+
+use super::table::Table;
+
+// Print the differences of every table between the baseline and the current analysis
+pub fn print(baseline: &AnalyisisResult, analysis: &AnalyisisResult, limit: usize, improvements: bool) {
+    print_totals(baseline, analysis);
+
+    let comparison = comparison::compare(&baseline.summary, &analysis.summary);
+
+    print_entries("Targets", &comparison.targets, |k| k.clone(), limit, improvements);
+    print_entries("Includes", &comparison.includes, |k| k.clone(), limit, improvements);
+    print_entries("Sources", &comparison.sources, |k| k.clone(), limit, improvements);
+    print_entries(
+        "Frontend operations",
+        &comparison.frontend_operations,
+        |k| format!("{} {}", k.1.short_name(), k.0),
+        limit,
+        improvements,
+    );
+    print_entries("Backend operations", &comparison.backend_operations, |k| k.clone(), limit, improvements);
+}
+
+fn secs(us: Option<u64>) -> String {
+    us.map(|us| format!("{:.2}", us as f64 * 1e-6)).unwrap_or_else(|| "-".to_string())
+}
+
+fn print_totals(baseline: &AnalyisisResult, analysis: &AnalyisisResult) {
+    let mut table = Table::new(&["Description", "Old", "New", "Δ"]);
+
+    // Last element is the number of decimals
+    let rows = [
+        ("Total files", baseline.summary.total_files() as f64, analysis.summary.total_files() as f64, 0),
+        ("Frontend [s]", baseline.summary.frontend_duration_sec(), analysis.summary.frontend_duration_sec(), 2),
+        ("Backend [s]", baseline.summary.backend_duration_sec(), analysis.summary.backend_duration_sec(), 2),
+        ("User time [s]", baseline.summary.inferred_used_time_secs(), analysis.summary.inferred_used_time_secs(), 2),
+    ];
+
+    for (description, old, new, precision) in rows {
+        table.push(vec![
+            description.to_string(),
+            format!("{:.*}", precision, old),
+            format!("{:.*}", precision, new),
+            format!("{:+.*}", precision, new - old),
+        ]);
+    }
+
+    table.print(&format!("Summary ({} -> {})", baseline.reload_path(), analysis.reload_path()));
+}
+
+fn print_entries<K>(
+    title: &str,
+    entries: &[DiffEntry<K>],
+    name: impl Fn(&K) -> String,
+    limit: usize,
+    improvements: bool,
+) {
+    let mut table = Table::new(&["Old [s]", "New [s]", "Δ [s]", "Δ [%]", "Status", "Name"]);
+
+    for entry in comparison::ordered(entries, improvements).take(limit) {
+        table.push(vec![
+            secs(entry.old_us),
+            secs(entry.new_us),
+            format!("{:+.2}", entry.delta_us() as f64 * 1e-6),
+            entry.delta_percent().map(|p| format!("{:+.1}", p)).unwrap_or_else(|| "-".to_string()),
+            entry.status().short_name().to_string(),
+            name(&entry.key),
+        ]);
+    }
+
+    let order = if improvements { "largest improvement first" } else { "largest regression first" };
+
+    table.print(&format!("{} ({})", title, order));
+}
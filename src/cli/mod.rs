@@ -0,0 +1,325 @@
+mod compare;
+mod report;
+mod table;
+mod variants;
+
+use std::process::ExitCode;
+
+use crate::model::BuildVariant;
+use crate::processing::budget::{self, Budget};
+use crate::processing::timeline::compute_parallelism;
+use crate::processing::variant_comparison;
+use crate::processing::AnalyisisResult;
+use table::Table;
+
+const USAGE: &str = "\
+Usage:
+    clang-build-time-processor                      Start the GUI
+    clang-build-time-processor report <PATH> [OPTIONS]
+    clang-build-time-processor compare <BASELINE> <PATH> [OPTIONS]
+    clang-build-time-processor check <PATH> [--baseline <BASELINE>] [--budget <FILE>] [OPTIONS]
+    clang-build-time-processor variants <PATH> [--variant <VARIANT>]... [OPTIONS]
+
+<PATH> and <BASELINE> are either build directories or JSON reports written with `--json`.
+
+Options:
+    --variant <VARIANT>   Build configuration like Debug or RelWithDebInfo, or SingleConfig [default: Release]
+                          variants: repeat to list the compared configurations, the first one is the
+                          reference of the ratios [default: all configurations of the build directory]
+    --top <N>             Number of rows printed for every table [default: 20]
+    --cores <N>           report: core count of the parallel efficiency [default: peak concurrency]
+    --json <FILE>         report: also write the full analysis as a JSON report
+    --csv <DIR>           report, variants: also write every table, with all rows, as CSV files into DIR
+    --improvements        compare: list the largest improvements first instead of the largest regressions
+    --by-ratio            variants: list the largest ratio of the last configuration to the first one first
+    --baseline <BASELINE> check: fail on regressions against this build directory or report
+    --budget <FILE>       check: fail when the limits of this JSON budget file are exceeded
+    -h, --help            Print this help";
+
+struct Options {
+    paths: Vec<String>,
+    build_variant: BuildVariant,
+    // Every `--variant` in the given order, `build_variant` is the last one
+    build_variants: Vec<BuildVariant>,
+    limit: usize,
+    cores: Option<usize>,
+    json_output: Option<String>,
+    csv_output: Option<String>,
+    improvements: bool,
+    by_ratio: bool,
+    baseline: Option<String>,
+    budget: Option<String>,
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let (command, rest) = args.split_first().map(|(c, r)| (c.as_str(), r)).unwrap_or(("--help", &[]));
+
+    let run_command = match command {
+        "report" => run_report,
+        "compare" => run_compare,
+        "check" => run_check,
+        "variants" => run_variants,
+        "-h" | "--help" => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        other => return usage_error(&format!("Unknown command: {}", other)),
+    };
+
+    match parse_options(rest).and_then(|options| run_command(&options)) {
+        Ok(exit_code) => exit_code,
+        Err(e) => usage_error(&e),
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("{}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        paths: Vec::new(),
+        build_variant: BuildVariant::default(),
+        build_variants: Vec::new(),
+        limit: 20,
+        cores: None,
+        json_output: None,
+        csv_output: None,
+        improvements: false,
+        by_ratio: false,
+        baseline: None,
+        budget: None,
+    };
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--variant" => {
+                let value = args.next().ok_or("Missing value for --variant")?;
+                options.build_variant = value.parse()?;
+                options.build_variants.push(options.build_variant.clone());
+            }
+            "--top" => {
+                let value = args.next().ok_or("Missing value for --top")?;
+                options.limit = value.parse().map_err(|_| format!("Invalid value for --top: {}", value))?;
+            }
+            "--cores" => {
+                let value = args.next().ok_or("Missing value for --cores")?;
+                let cores = value.parse().ok().filter(|cores| *cores > 0);
+                options.cores = Some(cores.ok_or_else(|| format!("Invalid value for --cores: {}", value))?);
+            }
+            "--json" => {
+                let value = args.next().ok_or("Missing value for --json")?;
+                options.json_output = Some(value.clone());
+            }
+            "--csv" => {
+                let value = args.next().ok_or("Missing value for --csv")?;
+                options.csv_output = Some(value.clone());
+            }
+            "--improvements" => {
+                options.improvements = true;
+            }
+            "--by-ratio" => {
+                options.by_ratio = true;
+            }
+            "--baseline" => {
+                let value = args.next().ok_or("Missing value for --baseline")?;
+                options.baseline = Some(value.clone());
+            }
+            "--budget" => {
+                let value = args.next().ok_or("Missing value for --budget")?;
+                options.budget = Some(value.clone());
+            }
+            other if other.starts_with('-') => {
+                return Err(format!("Unknown option: {}", other));
+            }
+            other => {
+                options.paths.push(other.to_string());
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+// Load a build directory or a report, printing the error if that fails
+fn load(path: &str, build_variant: &BuildVariant) -> Option<AnalyisisResult> {
+    match crate::processing::load_path(path, build_variant) {
+        Ok(analysis) => {
+            if analysis.report_path.is_none() && analysis.summary.total_files() == 0 {
+                let available: Vec<String> = crate::processing::discover_build_variants(path)
+                    .iter()
+                    .map(|d| d.to_string())
+                    .collect();
+
+                eprintln!("No trace files for the build variant {}, available: {}", build_variant, available.join(", "));
+            }
+
+            Some(analysis)
+        }
+        Err(e) => {
+            eprintln!("Analysis error: {}", e);
+            None
+        }
+    }
+}
+
+fn run_report(options: &Options) -> Result<ExitCode, String> {
+    let [path] = options.paths.as_slice() else {
+        return Err("Expected exactly one build path".to_string());
+    };
+
+    let Some(mut analysis) = load(path, &options.build_variant) else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    if options.cores.is_some() {
+        analysis.summary.parallelism = compute_parallelism(&analysis.summary, options.cores);
+    }
+
+    report::print(&analysis, options.limit);
+
+    if let Some(json_output) = &options.json_output {
+        if let Err(e) = crate::processing::report::write_report(&analysis, json_output) {
+            eprintln!("Export error: {}", e);
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    if let Some(csv_output) = &options.csv_output {
+        if let Err(e) = crate::processing::csv_export::write_all_csv(&analysis, std::path::Path::new(csv_output)) {
+            eprintln!("Export error: {}", e);
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_compare(options: &Options) -> Result<ExitCode, String> {
+    let [baseline_path, path] = options.paths.as_slice() else {
+        return Err("Expected a baseline path and a build path".to_string());
+    };
+
+    if options.json_output.is_some() || options.csv_output.is_some() {
+        return Err("--json and --csv are only supported by `report`".to_string());
+    }
+
+    let Some(baseline) = load(baseline_path, &options.build_variant) else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let Some(analysis) = load(path, &options.build_variant) else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    compare::print(&baseline, &analysis, options.limit, options.improvements);
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_check(options: &Options) -> Result<ExitCode, String> {
+    let [path] = options.paths.as_slice() else {
+        return Err("Expected exactly one build path".to_string());
+    };
+
+    if options.baseline.is_none() && options.budget.is_none() {
+        return Err("`check` needs --baseline, --budget or both".to_string());
+    }
+
+    let budget = match &options.budget {
+        Some(budget_path) => match Budget::from_path(budget_path) {
+            Ok(budget) => budget,
+            Err(e) => {
+                eprintln!("Budget error: {}", e);
+                return Ok(ExitCode::FAILURE);
+            }
+        },
+        None => Budget::default(),
+    };
+
+    let Some(analysis) = load(path, &options.build_variant) else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let mut violations = budget::check_budget(&analysis, &budget);
+
+    if let Some(baseline_path) = &options.baseline {
+        let Some(baseline) = load(baseline_path, &options.build_variant) else {
+            return Ok(ExitCode::FAILURE);
+        };
+
+        violations.extend(budget::check_regressions(&baseline, &analysis, &budget));
+    }
+
+    if violations.is_empty() {
+        println!("No budget violations");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut table = Table::new(&["Rule", "Value", "Limit", "Name"]);
+
+    for violation in violations.iter().take(options.limit) {
+        table.push(vec![
+            violation.rule.to_string(),
+            violation.value.clone(),
+            violation.limit.clone(),
+            violation.name.clone(),
+        ]);
+    }
+
+    table.print(&format!("{} budget violations (showing up to {})", violations.len(), options.limit));
+
+    Ok(ExitCode::FAILURE)
+}
+
+fn run_variants(options: &Options) -> Result<ExitCode, String> {
+    let [path] = options.paths.as_slice() else {
+        return Err("Expected exactly one build path".to_string());
+    };
+
+    if options.json_output.is_some() {
+        return Err("--json is only supported by `report`".to_string());
+    }
+
+    // Reports hold a single configuration
+    if std::path::Path::new(path).is_file() {
+        return Err("`variants` needs a build directory".to_string());
+    }
+
+    let build_variants = match options.build_variants.len() {
+        0 => variant_comparison::configurations(path),
+        _ => options.build_variants.clone(),
+    };
+
+    if build_variants.len() < 2 {
+        let names: Vec<String> = build_variants.iter().map(|v| v.to_string()).collect();
+        return Err(format!("Expected at least two configurations to compare, found: {}", names.join(", ")));
+    }
+
+    let mut analyses = Vec::new();
+
+    for build_variant in build_variants {
+        let Some(analysis) = load(path, &build_variant) else {
+            return Ok(ExitCode::FAILURE);
+        };
+
+        analyses.push((build_variant, analysis));
+    }
+
+    let summaries: Vec<_> = analyses.iter().map(|(build_variant, analysis)| (build_variant.clone(), &analysis.summary)).collect();
+    let comparison = variant_comparison::compare_variants(&summaries);
+
+    variants::print(&comparison, options.limit, options.by_ratio);
+
+    if let Some(csv_output) = &options.csv_output {
+        if let Err(e) = crate::processing::csv_export::write_variant_comparison_csv(&comparison, std::path::Path::new(csv_output)) {
+            eprintln!("Export error: {}", e);
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,365 @@
+use crate::processing::namespace_tree::NamespaceNode;
+use crate::processing::AnalyisisResult;
+
+use super::table::Table;
+
+// Print the same numbers as the SMRY/INCS/SRCS/FRNT/BCKN/NSPC/EVNT panes, limited to the top `limit` rows
+pub fn print(analysis: &AnalyisisResult, limit: usize) {
+    print_totals(analysis);
+    print_parallelism(analysis);
+    print_critical_path(analysis, limit);
+    print_build_steps(analysis, limit);
+    print_targets(analysis, limit);
+    print_includes(analysis, limit);
+    print_sources(analysis, limit);
+    print_flag_groups(analysis, limit);
+    print_frontend_operations(analysis, limit);
+    print_templates(analysis, limit);
+    print_backend_operations(analysis, limit);
+    print_passes(analysis, limit);
+    print_namespaces(analysis, limit);
+    print_events(analysis, limit);
+}
+
+fn secs(us: u64) -> String {
+    format!("{:.2}", us as f64 * 1e-6)
+}
+
+fn avg_ms(us: u64, num: usize) -> String {
+    format!("{:.2}", us as f64 * 1e-3 / num as f64)
+}
+
+fn print_totals(analysis: &AnalyisisResult) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["Description", "Value"]);
+
+    let mut rows = vec![
+        ("Selected path", analysis.selected_path.clone()),
+        ("Resolved path", analysis.resolved_cmake_files_path.clone()),
+        ("Build variant", analysis.build_variant.to_string()),
+        ("Total files", summary.total_files().to_string()),
+        ("Total valid", summary.total_valid_files.to_string()),
+        ("Total invalid", summary.total_invalid_files.to_string()),
+        ("Frontend [s]", format!("{:.2}", summary.frontend_duration_sec())),
+        ("Backend [s]", format!("{:.2}", summary.backend_duration_sec())),
+        ("Backend single events [s]", format!("{:.2}", summary.backend_duration_single_events_sec())),
+        ("User time [s]", format!("{:.2}", summary.inferred_used_time_secs())),
+    ];
+
+    if let Some(report_path) = &analysis.report_path {
+        rows.insert(2, ("Loaded report", report_path.clone()));
+    }
+
+    for (description, value) in rows {
+        table.push(vec![description.to_string(), value]);
+    }
+
+    table.print("Summary");
+}
+
+fn print_parallelism(analysis: &AnalyisisResult) {
+    let parallelism = &analysis.summary.parallelism;
+
+    let mut table = Table::new(&["Description", "Value"]);
+
+    let rows = [
+        ("CPU time [s]", secs(parallelism.cpu_time_us)),
+        ("Wall time [s]", secs(parallelism.wall_time_us)),
+        ("Average concurrency", format!("{:.2}", parallelism.average_concurrency)),
+        ("Peak concurrency", parallelism.peak_concurrency.to_string()),
+        ("Cores", parallelism.cores.to_string()),
+        ("Parallel efficiency [%]", format!("{:.1}", parallelism.parallel_efficiency * 100.0)),
+    ];
+
+    for (description, value) in rows {
+        table.push(vec![description.to_string(), value]);
+    }
+
+    table.print("Parallelism");
+
+    let mut table = Table::new(&["Start [s]", "Duration [s]"]);
+
+    for stretch in parallelism.sequential_stretches.iter() {
+        table.push(vec![secs(stretch.start_us), secs(stretch.duration_us)]);
+    }
+
+    table.print("Longest sequential stretches (at most one compile running)");
+}
+
+fn print_critical_path(analysis: &AnalyisisResult, limit: usize) {
+    let critical_path = &analysis.summary.critical_path;
+
+    let mut table = Table::new(&["Step", "Duration [s]", "Share [%]", "Target"]);
+
+    for i in critical_path.largest_contribution_indices.iter().take(limit) {
+        let step = &critical_path.steps[*i];
+
+        table.push(vec![
+            (i + 1).to_string(),
+            secs(step.duration_us),
+            format!("{:.1}", step.duration_us as f64 * 100.0 / critical_path.duration_us.max(1) as f64),
+            step.target_name.clone(),
+        ]);
+    }
+
+    table.print(&format!("Critical path, {} s over {} targets (by contribution)", secs(critical_path.duration_us), critical_path.steps.len()));
+}
+
+fn print_build_steps(analysis: &AnalyisisResult, limit: usize) {
+    let ninja = &analysis.summary.ninja;
+
+    if analysis.summary.ninja_steps.is_empty() {
+        return;
+    }
+
+    let mut table = Table::new(&["Description", "Value"]);
+
+    let rows = [
+        ("Steps", analysis.summary.ninja_steps.len().to_string()),
+        ("Traced compile steps", ninja.traced_compile_steps.to_string()),
+        ("Wall time [s]", secs(ninja.wall_time_us)),
+        ("Not compiling [s]", secs(ninja.not_compiling_us)),
+        ("Compile [s]", secs(ninja.totals.compile_us)),
+        ("Link [s]", secs(ninja.totals.link_us)),
+        ("Other [s]", secs(ninja.totals.other_us)),
+    ];
+
+    for (description, value) in rows {
+        table.push(vec![description.to_string(), value]);
+    }
+
+    table.print("Build steps (.ninja_log)");
+
+    let mut table = Table::new(&["Compile [s]", "Link [s]", "Other [s]", "Total [s]", "Target"]);
+
+    for target_name in ninja.target_steps_largest_total_indices.iter().take(limit) {
+        let steps = &ninja.target_steps[target_name];
+
+        table.push(vec![
+            secs(steps.compile_us),
+            secs(steps.link_us),
+            secs(steps.other_us),
+            secs(steps.total_us()),
+            if target_name.is_empty() { "(no target)".to_string() } else { target_name.clone() },
+        ]);
+    }
+
+    table.print("Build steps by target (by total time)");
+}
+
+fn print_targets(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["Total files", "Frontend [s]", "Backend [s]", "Start [s]", "End [s]", "Target"]);
+
+    for target_name in summary.target_summaries_largest_duration_indices.iter().take(limit) {
+        let target_summary = &summary.target_summaries[target_name];
+
+        // Targets with only invalid trace files have no event times
+        let relative_first_time = target_summary.first_event_time.saturating_sub(summary.first_event_time) as f64 * 1e-6;
+        let relative_last_time = target_summary.last_event_time.saturating_sub(summary.first_event_time) as f64 * 1e-6;
+
+        table.push(vec![
+            target_summary.total_files.to_string(),
+            format!("{:.2}", target_summary.frontend_duration_sec()),
+            format!("{:.2}", target_summary.backend_duration_sec()),
+            format!("{:.2}", relative_first_time),
+            format!("{:.2}", relative_last_time),
+            target_name.clone(),
+        ]);
+    }
+
+    table.print("Targets (by duration)");
+}
+
+fn print_includes(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "∑ Self [s]", "∑ Total [s]", "AVG Self [ms]", "AVG Total [ms]", "Include Path"]);
+
+    for include in summary.frontend_file_largest_time_indices.iter().take(limit) {
+        let include_summary = &summary.frontend_file_process_summaries[include];
+
+        table.push(vec![
+            include_summary.num.to_string(),
+            secs(include_summary.self_time_us),
+            secs(include_summary.total_time_us),
+            avg_ms(include_summary.self_time_us, include_summary.num),
+            avg_ms(include_summary.total_time_us, include_summary.num),
+            include.clone(),
+        ]);
+    }
+
+    table.print("Includes (by total time)");
+}
+
+fn print_sources(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["Total [s]", "Front [s]", "Back [s]", "Source Path"]);
+
+    for source in summary.source_file_largest_total_time_indices.iter().take(limit) {
+        let source_summary = &summary.source_file_process_summaries[source];
+
+        table.push(vec![
+            secs(source_summary.total_time_us),
+            secs(source_summary.total_frontend_time_us),
+            secs(source_summary.total_backend_time_us),
+            source.clone(),
+        ]);
+    }
+
+    table.print("Sources (by total time)");
+}
+
+fn print_flag_groups(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    if summary.compile_commands.is_empty() {
+        return;
+    }
+
+    let mut table = Table::new(&["#", "∑ Total [s]", "Most expensive", "Flags"]);
+
+    for group in summary.flag_groups.iter().take(limit) {
+        table.push(vec![
+            group.sources.len().to_string(),
+            secs(group.total_time_us),
+            group.sources.first().cloned().unwrap_or_default(),
+            group.description(),
+        ]);
+    }
+
+    table.print("Flag groups (by size, compared to the flags of most translation units)");
+}
+
+fn print_frontend_operations(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "Type", "∑ Self [s]", "∑ Total [s]", "AVG Self [ms]", "AVG Total [ms]", "Name"]);
+
+    for key in summary.frontend_operation_largest_total_time_indices.iter().take(limit) {
+        let operation_summary = &summary.frontend_operation_summaries[key];
+
+        table.push(vec![
+            operation_summary.num.to_string(),
+            key.1.short_name().to_string(),
+            secs(operation_summary.self_time_us),
+            secs(operation_summary.total_time_us),
+            avg_ms(operation_summary.self_time_us, operation_summary.num),
+            avg_ms(operation_summary.total_time_us, operation_summary.num),
+            key.0.clone(),
+        ]);
+    }
+
+    table.print("Frontend operations (by total time)");
+}
+
+fn print_templates(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "Inst.", "Type", "∑ Self [s]", "∑ Total [s]", "AVG Total [ms]", "Template"]);
+
+    for key in summary.template_group_largest_total_time_indices.iter().take(limit) {
+        let group = &summary.template_groups[key];
+
+        table.push(vec![
+            group.num.to_string(),
+            group.instantiations.len().to_string(),
+            key.1.short_name().to_string(),
+            secs(group.self_time_us),
+            secs(group.total_time_us),
+            avg_ms(group.total_time_us, group.num),
+            key.0.clone(),
+        ]);
+    }
+
+    table.print("Templates (by total time)");
+}
+
+fn print_backend_operations(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "∑ Total [s]", "AVG Total [ms]", "Name"]);
+
+    for key in summary.backend_operation_largest_total_time_indices.iter().take(limit) {
+        let operation_summary = &summary.backend_operation_summaries[key];
+
+        table.push(vec![
+            operation_summary.num.to_string(),
+            secs(operation_summary.total_time_us),
+            avg_ms(operation_summary.total_time_us, operation_summary.num),
+            key.clone(),
+        ]);
+    }
+
+    table.print("Backend operations (by total time)");
+}
+
+fn print_passes(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "∑ Self [s]", "∑ Total [s]", "AVG Self [ms]", "AVG Total [ms]", "Pass"]);
+
+    for name in summary.pass_largest_self_time_indices.iter().take(limit) {
+        let pass_summary = &summary.pass_summaries[name];
+
+        table.push(vec![
+            pass_summary.num.to_string(),
+            secs(pass_summary.self_time_us),
+            secs(pass_summary.total_time_us),
+            avg_ms(pass_summary.self_time_us, pass_summary.num),
+            avg_ms(pass_summary.total_time_us, pass_summary.num),
+            name.clone(),
+        ]);
+    }
+
+    table.print("LLVM passes (by self time)");
+}
+
+// The largest namespaces with their largest nested namespaces, two levels deep
+fn print_namespaces(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "Frontend Self [s]", "Backend [s]", "∑ Total [s]", "Namespace"]);
+
+    let row = |path: String, node: &NamespaceNode| vec![
+        node.num.to_string(),
+        secs(node.frontend_time_us),
+        secs(node.backend_time_us),
+        secs(node.total_time_us()),
+        path,
+    ];
+
+    for (name, node) in summary.namespace_tree.sorted_children(|n| n.total_time_us()).into_iter().take(limit) {
+        table.push(row(name.clone(), node));
+
+        for (child_name, child) in node.sorted_children(|n| n.total_time_us()).into_iter().take(3) {
+            table.push(row(format!("  {}::{}", name, child_name), child));
+        }
+    }
+
+    table.print("Namespaces (by total time)");
+}
+
+fn print_events(analysis: &AnalyisisResult, limit: usize) {
+    let summary = &analysis.summary;
+
+    let mut table = Table::new(&["#", "∑ Self [s]", "∑ Total [s]", "AVG Self [ms]", "AVG Total [ms]", "Event"]);
+
+    for name in summary.event_name_largest_total_time_indices.iter().take(limit) {
+        let event_summary = &summary.event_name_summaries[name];
+
+        table.push(vec![
+            event_summary.num.to_string(),
+            secs(event_summary.self_time_us),
+            secs(event_summary.total_time_us),
+            avg_ms(event_summary.self_time_us, event_summary.num),
+            avg_ms(event_summary.total_time_us, event_summary.num),
+            name.clone(),
+        ]);
+    }
+
+    table.print("Events (by total time)");
+}
@@ -0,0 +1,60 @@
+// Minimal plain-text table printer used by the command-line reports.
+//
+// All columns are right aligned except the last one, which is left aligned and
+// never truncated. Put the (potentially very long) name column last so that the
+// numeric columns stay aligned.
+
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Table {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        debug_assert_eq!(row.len(), self.headers.len());
+        self.rows.push(row);
+    }
+
+    pub fn print(&self, title: &str) {
+        println!("== {} ==", title);
+
+        let column_count = self.headers.len();
+
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+
+        for row in self.rows.iter() {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+
+        let format_row = |row: &[String]| {
+            let mut line = String::new();
+
+            for (i, cell) in row.iter().enumerate() {
+                if i + 1 == column_count {
+                    line.push_str(cell);
+                } else {
+                    line.push_str(&format!("{:>width$}  ", cell, width = widths[i]));
+                }
+            }
+
+            line
+        };
+
+        println!("{}", format_row(&self.headers));
+
+        for row in self.rows.iter() {
+            println!("{}", format_row(row));
+        }
+
+        println!();
+    }
+}
@@ -0,0 +1,95 @@
+use crate::processing::variant_comparison::{VariantComparison, VariantTable};
+
+use super::table::Table;
+
+// Print every table with a column per configuration and the ratios to the first configuration
+pub fn print(comparison: &VariantComparison, limit: usize, by_ratio: bool) {
+    print_totals(comparison);
+
+    print_entries(comparison, "Targets", &comparison.targets, |k| k.clone(), limit, by_ratio);
+    print_entries(comparison, "Includes", &comparison.includes, |k| k.clone(), limit, by_ratio);
+    print_entries(comparison, "Sources", &comparison.sources, |k| k.clone(), limit, by_ratio);
+    print_entries(
+        comparison,
+        "Frontend operations",
+        &comparison.frontend_operations,
+        |k| format!("{} {}", k.1.short_name(), k.0),
+        limit,
+        by_ratio,
+    );
+    print_entries(comparison, "Backend operations", &comparison.backend_operations, |k| k.clone(), limit, by_ratio);
+}
+
+// `Release [s]`, ..., then `Release/Debug`, ... and finally the name
+fn variant_headers(comparison: &VariantComparison) -> Vec<String> {
+    let build_variants = &comparison.build_variants;
+
+    let mut headers: Vec<String> = build_variants.iter().map(|v| format!("{} [s]", v)).collect();
+    headers.extend(build_variants.iter().skip(1).map(|v| format!("{}/{}", v, build_variants[0])));
+
+    headers
+}
+
+fn ratio(ratio: Option<f64>) -> String {
+    ratio.map(|r| format!("{:.2}", r)).unwrap_or_else(|| "-".to_string())
+}
+
+fn print_totals(comparison: &VariantComparison) {
+    // Same columns as the tables, without the unit as the rows have different ones
+    let mut headers = vec!["Description".to_string()];
+    headers.extend(variant_headers(comparison).into_iter().map(|h| h.trim_end_matches(" [s]").to_string()));
+
+    let mut table = Table::new(&headers.iter().map(|h| h.as_str()).collect::<Vec<_>>());
+
+    let totals = &comparison.totals;
+
+    // Last element is the number of decimals
+    let rows = [
+        ("Total files", totals.iter().map(|t| t.total_files as f64).collect::<Vec<_>>(), 0),
+        ("Frontend [s]", totals.iter().map(|t| t.frontend_sec).collect(), 2),
+        ("Backend [s]", totals.iter().map(|t| t.backend_sec).collect(), 2),
+        ("User time [s]", totals.iter().map(|t| t.inferred_used_time_sec).collect(), 2),
+    ];
+
+    for (description, values, precision) in rows {
+        let mut row = vec![description.to_string()];
+        row.extend(values.iter().map(|value| format!("{:.*}", precision, value)));
+        row.extend(values.iter().skip(1).map(|value| ratio(Some(value / values[0]).filter(|r| r.is_finite()))));
+
+        table.push(row);
+    }
+
+    let names: Vec<String> = comparison.build_variants.iter().map(|v| v.to_string()).collect();
+
+    table.print(&format!("Summary ({})", names.join(", ")));
+}
+
+fn print_entries<K>(
+    comparison: &VariantComparison,
+    title: &str,
+    table: &VariantTable<K>,
+    name: impl Fn(&K) -> String,
+    limit: usize,
+    by_ratio: bool,
+) {
+    let mut headers = variant_headers(comparison);
+    headers.push("Name".to_string());
+
+    let mut out = Table::new(&headers.iter().map(|h| h.as_str()).collect::<Vec<_>>());
+
+    for entry in table.ordered(by_ratio).take(limit) {
+        let mut row: Vec<String> = entry.times_us
+            .iter()
+            .map(|us| us.map(|us| format!("{:.2}", us as f64 * 1e-6)).unwrap_or_else(|| "-".to_string()))
+            .collect();
+
+        row.extend((1..entry.times_us.len()).map(|index| ratio(entry.ratio(index))));
+        row.push(name(&entry.key));
+
+        out.push(row);
+    }
+
+    let order = if by_ratio { "largest ratio first" } else { "largest time first" };
+
+    out.print(&format!("{} ({})", title, order));
+}
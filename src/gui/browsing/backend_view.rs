@@ -1,12 +1,18 @@
 use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
 use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
 
+use crate::processing::name_filter::{self, NameFilter};
 use crate::processing::AnalyisisResult;
 
-use crate::gui::{style::MONO, Message};
+use crate::gui::{style::MONO, BrowsingBackendSelectedOption, Message};
 
 
-pub fn view<'a>(state: &'a AnalyisisResult, full_name_display: &'a Option<String>) -> Element<'a, Message> {
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    selected_option: BrowsingBackendSelectedOption,
+    full_name_display: &'a Option<String>,
+    filter: Option<&'a NameFilter>,
+) -> Element<'a, Message> {
 
 
     if let Some(full_name) = full_name_display {
@@ -52,88 +58,208 @@ pub fn view<'a>(state: &'a AnalyisisResult, full_name_display: &'a Option<String
         ].spacing(4).into()
 
     } else {
-        let limit = 100;
-
-        let mut num_occurences = Column::new()
-            .spacing(6)
-            .align_x(alignment::Alignment::End)
-            .push(text("#").font(MONO).size(12))
-            .push(text("").font(MONO).size(4));
-
-        let mut buttons = Column::new()
-            .spacing(6)
-            .push(text(" ").font(MONO).size(12))
-            .push(text("").font(MONO).size(4));
-
-        let mut name_col = Column::new()
-            .spacing(6)
-            .push(text("Name").font(MONO).size(12))
-            .push(text("").font(MONO).size(4));
-
-        let mut total_time_col = Column::new()
-            .spacing(6)
-            .align_x(alignment::Alignment::End)
-            .push(text("∑ Total [s]").font(MONO).size(12))
-            .push(text("").font(MONO).size(4));
-
-        let mut total_time_avg_col = Column::new()
-            .spacing(6)
-            .align_x(alignment::Alignment::End)
-            .push(text("AVG Total [ms]").font(MONO).size(12))
-            .push(text("").font(MONO).size(4));
-
-        let items_to_display = &state.summary.backend_operation_largest_total_time_indices;
-
-        for key in items_to_display.iter().take(limit) {
-
-            buttons = buttons.push(
-                button(text("DISP").font(MONO).size(12))
-                    .padding(0)
-                    .on_press(Message::BrowseBackendFullNameClicked(key.clone()))
-            );
-
-            name_col = name_col.push(
-                text(limit_string_name(key)).font(MONO).size(12)
-            );
-
-            let summary = state.summary.backend_operation_summaries.get(key).unwrap();
-
-            total_time_col = total_time_col.push(
-                text(format!("{:.2}", summary.total_time_us as f64 * 1e-6))
-                    .font(MONO)
-                    .size(12)
-            );
-
-            num_occurences = num_occurences.push(
-                text(summary.num.to_string())
-                    .font(MONO)
-                    .size(12)
-            );
-
-            total_time_avg_col = total_time_avg_col.push(
-                text(format!("{:.2}", summary.total_time_us as f64 * 1e-3 / summary.num as f64))
-                    .font(MONO)
-                    .size(12)
-            );
-        }
-
-        let table = row![
-            num_occurences,
-            buttons,
-            name_col,
-            total_time_col,
-            total_time_avg_col,
+        let is_pass_option = !matches!(selected_option, BrowsingBackendSelectedOption::Symbols);
+
+        let top_row = row![
+            button(text("SYMS").font(MONO))
+                .on_press(Message::BrowseBackendSymbolsClicked),
+            button(text("PASS").font(MONO))
+                .on_press(Message::BrowseBackendPassTotalTimeClicked),
+            text(" ").font(MONO),
+            button(text("TOTAL").font(MONO))
+                .on_press_maybe(is_pass_option.then_some(Message::BrowseBackendPassTotalTimeClicked)),
+            button(text("SELF").font(MONO))
+                .on_press_maybe(is_pass_option.then_some(Message::BrowseBackendPassSelfTimeClicked)),
         ]
-            .spacing(12);
+            .spacing(4);
+
+        let table = match selected_option {
+            BrowsingBackendSelectedOption::Symbols => symbols_table(state, filter),
+            BrowsingBackendSelectedOption::PassTotalTime => passes_table(state, &state.summary.pass_largest_total_time_indices, filter),
+            BrowsingBackendSelectedOption::PassSelfTime => passes_table(state, &state.summary.pass_largest_self_time_indices, filter),
+        };
 
         let table_content = scrollable(table)
             .width(iced::Length::Fill)
             .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
 
-        table_content.into()
+        column![
+            top_row,
+            horizontal_rule(2),
+            table_content,
+        ]
+            .spacing(4)
+            .into()
     }
 }
 
+fn symbols_table<'a>(state: &'a AnalyisisResult, filter: Option<&'a NameFilter>) -> Element<'a, Message> {
+    let limit = 100;
+
+    let mut num_occurences = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut buttons = Column::new()
+        .spacing(6)
+        .push(text(" ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Name").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_avg_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("AVG Total [ms]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let items_to_display = &state.summary.backend_operation_largest_total_time_indices;
+
+    for key in name_filter::filtered(items_to_display, filter, |k| k).take(limit) {
+
+        buttons = buttons.push(
+            button(text("DISP").font(MONO).size(12))
+                .padding(0)
+                .on_press(Message::BrowseBackendFullNameClicked(key.clone()))
+        );
+
+        name_col = name_col.push(
+            text(limit_string_name(key)).font(MONO).size(12)
+        );
+
+        let summary = state.summary.backend_operation_summaries.get(key).unwrap();
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        num_occurences = num_occurences.push(
+            text(summary.num.to_string())
+                .font(MONO)
+                .size(12)
+        );
+
+        total_time_avg_col = total_time_avg_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-3 / summary.num as f64))
+                .font(MONO)
+                .size(12)
+        );
+    }
+
+    row![
+        num_occurences,
+        buttons,
+        name_col,
+        total_time_col,
+        total_time_avg_col,
+    ]
+        .spacing(12)
+        .into()
+}
+
+fn passes_table<'a>(state: &'a AnalyisisResult, order: &'a [String], filter: Option<&'a NameFilter>) -> Element<'a, Message> {
+    let limit = 100;
+
+    let mut num_occurences = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Pass").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Self [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_share_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Self [% backend]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_avg_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("AVG Total [ms]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let backend_us = state.summary.backend_duration_total_us.max(1);
+
+    for name in name_filter::filtered(order, filter, |k| k).take(limit) {
+        let summary = &state.summary.pass_summaries[name];
+
+        num_occurences = num_occurences.push(
+            text(summary.num.to_string())
+                .font(MONO)
+                .size(12)
+        );
+
+        name_col = name_col.push(
+            text(limit_string_name(name)).font(MONO).size(12)
+        );
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", summary.self_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        self_share_col = self_share_col.push(
+            text(format!("{:.1}", summary.self_time_us as f64 * 100.0 / backend_us as f64))
+                .font(MONO)
+                .size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        total_time_avg_col = total_time_avg_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-3 / summary.num as f64))
+                .font(MONO)
+                .size(12)
+        );
+    }
+
+    row![
+        num_occurences,
+        name_col,
+        self_time_col,
+        self_share_col,
+        total_time_col,
+        total_time_avg_col,
+    ]
+        .spacing(12)
+        .into()
+}
+
 // Fill the string with `...` in the misdle if it's too long
 fn limit_string_name(name: &str) -> String {
     const MAX_LEN: usize = 120;
@@ -0,0 +1,181 @@
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, text_input, Column}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::comparison::{self, DiffEntry};
+
+use crate::gui::{style::MONO, AnalyzingFilesState, BrowsingComparisonSelectedOption, BrowsingComparisonSelectedTable, Message};
+
+use super::limit_string_name;
+
+
+pub fn view<'a>(
+    state: &'a AnalyzingFilesState,
+    selected_table: BrowsingComparisonSelectedTable,
+    selected_option: BrowsingComparisonSelectedOption,
+) -> Element<'a, Message> {
+
+    let baseline_row = match &state.baseline_loading {
+        Some(loading) => row![
+            button(text("CANCEL").font(MONO))
+                .on_press(Message::BrowseComparisonCancelClicked)
+                .style(iced::widget::button::danger),
+            text(format!(
+                "Analyzing the baseline {}: {}/{} files",
+                state.baseline_input,
+                loading.progress.files_processed(),
+                loading.progress.files_enumerated,
+            )).font(MONO).size(12),
+        ]
+            .align_y(alignment::Alignment::Center),
+        None => row![
+            text_input("Baseline build path or report file", &state.baseline_input)
+                .font(MONO)
+                .on_input(Message::BrowseComparisonBaselineInputChanged)
+                .on_submit(Message::BrowseComparisonLoadClicked),
+            button(text("LOAD").font(MONO))
+                .on_press(Message::BrowseComparisonLoadClicked),
+        ],
+    }
+        .spacing(4);
+
+    let top_row = row![
+        button(text("TRGT").font(MONO))
+            .on_press(Message::BrowseComparisonTargetsClicked),
+        button(text("INCS").font(MONO))
+            .on_press(Message::BrowseComparisonIncludesClicked),
+        button(text("SRCS").font(MONO))
+            .on_press(Message::BrowseComparisonSourcesClicked),
+        button(text("FRNT").font(MONO))
+            .on_press(Message::BrowseComparisonFrontendClicked),
+        button(text("BCKN").font(MONO))
+            .on_press(Message::BrowseComparisonBackendClicked),
+        text(" ").font(MONO),
+        button(text("REGR").font(MONO))
+            .on_press(Message::BrowseComparisonRegressionsClicked),
+        button(text("IMPR").font(MONO))
+            .on_press(Message::BrowseComparisonImprovementsClicked),
+    ]
+        .spacing(4);
+
+    let Some(comparison_state) = &state.comparison else {
+        return column![
+            baseline_row,
+            horizontal_rule(2),
+            text("Load a baseline (build directory or JSON report) to compare against").font(MONO).size(12),
+        ]
+            .spacing(4)
+            .into();
+    };
+
+    let comparison = &comparison_state.comparison;
+    let improvements_first = matches!(selected_option, BrowsingComparisonSelectedOption::Improvements);
+
+    let table = match selected_table {
+        BrowsingComparisonSelectedTable::Targets => table(&comparison.targets, |k| k.clone(), improvements_first),
+        BrowsingComparisonSelectedTable::Includes => table(&comparison.includes, |k| k.clone(), improvements_first),
+        BrowsingComparisonSelectedTable::Sources => table(&comparison.sources, |k| k.clone(), improvements_first),
+        BrowsingComparisonSelectedTable::Frontend => table(
+            &comparison.frontend_operations,
+            |k| format!("{} {}", k.1.short_name(), limit_string_name(&k.0)),
+            improvements_first
+        ),
+        BrowsingComparisonSelectedTable::Backend => table(&comparison.backend_operations, |k| limit_string_name(k), improvements_first),
+    };
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        baseline_row,
+        text(format!("Baseline: {}", comparison_state.baseline.reload_path())).font(MONO).size(12),
+        top_row,
+        horizontal_rule(2),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
+
+fn table<'a, K>(entries: &'a [DiffEntry<K>], name: impl Fn(&K) -> String, improvements_first: bool) -> Element<'a, Message> {
+    let limit = 100;
+
+    let mut old_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Old [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut new_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("New [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut delta_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Δ [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut delta_percent_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Δ [%]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut status_col = Column::new()
+        .spacing(6)
+        .push(text("    ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Name").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    for entry in comparison::ordered(entries, improvements_first).take(limit) {
+        old_time_col = old_time_col.push(
+            text(entry.old_us.map(|us| format!("{:.2}", us as f64 * 1e-6)).unwrap_or("-".to_string()))
+                .font(MONO)
+                .size(12)
+        );
+
+        new_time_col = new_time_col.push(
+            text(entry.new_us.map(|us| format!("{:.2}", us as f64 * 1e-6)).unwrap_or("-".to_string()))
+                .font(MONO)
+                .size(12)
+        );
+
+        delta_col = delta_col.push(
+            text(format!("{:+.2}", entry.delta_us() as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        delta_percent_col = delta_percent_col.push(
+            text(entry.delta_percent().map(|p| format!("{:+.1}", p)).unwrap_or("-".to_string()))
+                .font(MONO)
+                .size(12)
+        );
+
+        status_col = status_col.push(
+            text(entry.status().short_name()).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(
+            text(name(&entry.key)).font(MONO).size(12)
+        );
+    }
+
+    row![
+        old_time_col,
+        new_time_col,
+        delta_col,
+        delta_percent_col,
+        status_col,
+        name_col,
+    ]
+        .spacing(12)
+        .into()
+}
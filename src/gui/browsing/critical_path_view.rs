@@ -0,0 +1,95 @@
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::AnalyisisResult;
+
+use crate::gui::{style::MONO, BrowsingCriticalPathSelectedOption, Message};
+
+
+pub fn view(state: &AnalyisisResult, selected_option: BrowsingCriticalPathSelectedOption) -> Element<'_, Message> {
+
+    let top_row = row![
+        button(text("CHAIN").font(MONO))
+            .on_press(Message::BrowseCriticalPathPaneChainClicked),
+        button(text("CONTRIB").font(MONO))
+            .on_press(Message::BrowseCriticalPathPaneContributionClicked),
+    ]
+        .spacing(4);
+
+    let critical_path = &state.summary.critical_path;
+
+    let description = if state.summary.target_dependencies.is_empty() {
+        "No target dependencies found in the build tree, every target is a chain of its own".to_string()
+    } else {
+        format!(
+            "Critical path of {} targets: {:.2} s, the build cannot finish faster with any number of cores",
+            critical_path.steps.len(),
+            critical_path.duration_us as f64 * 1e-6,
+        )
+    };
+
+    let mut step_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Step").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut duration_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Duration [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut share_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Share [%]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Target").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let order: Vec<usize> = match selected_option {
+        BrowsingCriticalPathSelectedOption::Chain => (0..critical_path.steps.len()).collect(),
+        BrowsingCriticalPathSelectedOption::Contribution => critical_path.largest_contribution_indices.clone(),
+    };
+
+    for i in order {
+        let step = &critical_path.steps[i];
+
+        step_col = step_col.push(text((i + 1).to_string()).font(MONO).size(12));
+
+        duration_col = duration_col.push(
+            text(format!("{:.2}", step.duration_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        share_col = share_col.push(
+            text(format!("{:.1}", step.duration_us as f64 * 100.0 / critical_path.duration_us.max(1) as f64)).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(text(&step.target_name).font(MONO).size(12));
+    }
+
+    let table = row![
+        step_col,
+        duration_col,
+        share_col,
+        name_col,
+    ]
+        .spacing(12);
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        top_row,
+        horizontal_rule(2),
+        text(description).font(MONO).size(12),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
@@ -0,0 +1,201 @@
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::name_filter::{self, NameFilter};
+use crate::processing::{summary::EventSummary, AnalyisisResult};
+
+use crate::gui::{style::MONO, BrowsingEventsSelectedOption, Message};
+
+use super::limit_string_name;
+
+
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    selected_option: BrowsingEventsSelectedOption,
+    selected_name: &'a Option<String>,
+    filter: Option<&'a NameFilter>,
+) -> Element<'a, Message> {
+
+    let mut top_row = row![]
+        .spacing(4);
+
+    if selected_name.is_some() {
+        top_row = top_row.push(
+            button(text("BACK").font(MONO))
+                .on_press(Message::BrowseEventsNameClosed)
+        );
+    }
+
+    let top_row = top_row
+        .push(
+            button(text("TOTAL").font(MONO))
+                .on_press(Message::BrowseEventsTotalTimeClicked)
+        )
+        .push(
+            button(text("SELF").font(MONO))
+                .on_press(Message::BrowseEventsSelfTimeClicked)
+        );
+
+    let summary = &state.summary;
+
+    let table = match selected_name {
+        None => {
+            let order = match selected_option {
+                BrowsingEventsSelectedOption::TotalTime => &summary.event_name_largest_total_time_indices,
+                BrowsingEventsSelectedOption::SelfTime => &summary.event_name_largest_self_time_indices,
+            };
+
+            table(
+                "Event",
+                name_filter::filtered(order, filter, |k| k).map(|name| (name.clone(), &summary.event_name_summaries[name], Some(name.clone())))
+            )
+        }
+        Some(selected_name) => {
+            let order = match selected_option {
+                BrowsingEventsSelectedOption::TotalTime => &summary.event_largest_total_time_indices,
+                BrowsingEventsSelectedOption::SelfTime => &summary.event_largest_self_time_indices,
+            };
+
+            table(
+                "Detail",
+                name_filter::filtered(order, filter, |k| &k.1)
+                    .filter(|key| key.0 == *selected_name)
+                    .map(|key| (limit_string_name(&key.1), &summary.event_summaries[key], None))
+            )
+        }
+    };
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    let mut outer = column![
+        top_row,
+    ]
+        .spacing(4);
+
+    if let Some(selected_name) = selected_name {
+        outer = outer.push(text(format!("Details of {}", selected_name)).font(MONO).size(12));
+    }
+
+    outer
+        .push(horizontal_rule(2))
+        .push(content)
+        .into()
+}
+
+// Rows are the displayed name, the times and the event name whose details the DTLS button opens
+fn table<'a>(
+    name_header: &'static str,
+    rows: impl Iterator<Item = (String, &'a EventSummary, Option<String>)>,
+) -> Element<'a, Message> {
+    let limit = 100;
+
+    let mut num_occurences = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut detail_buttons = Column::new()
+        .spacing(6)
+        .push(text(" ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text(name_header).font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Self [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_time_avg_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("AVG Self [ms]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_avg_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("AVG Total [ms]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut has_details = false;
+
+    for (name, summary, details_name) in rows.take(limit) {
+        num_occurences = num_occurences.push(
+            text(summary.num.to_string())
+                .font(MONO)
+                .size(12)
+        );
+
+        if let Some(details_name) = details_name {
+            has_details = true;
+
+            detail_buttons = detail_buttons.push(
+                button(text("DTLS").font(MONO).size(12))
+                    .padding(0)
+                    .on_press(Message::BrowseEventsNameClicked(details_name))
+            );
+        }
+
+        // Events without a detail are only listed by their name
+        let name = if name.is_empty() { "-".to_string() } else { name };
+
+        name_col = name_col.push(
+            text(name).font(MONO).size(12)
+        );
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", summary.self_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        self_time_avg_col = self_time_avg_col.push(
+            text(format!("{:.2}", summary.self_time_us as f64 * 1e-3 / summary.num as f64))
+                .font(MONO)
+                .size(12)
+        );
+
+        total_time_avg_col = total_time_avg_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-3 / summary.num as f64))
+                .font(MONO)
+                .size(12)
+        );
+    }
+
+    let mut table = row![
+        num_occurences,
+    ]
+        .spacing(12);
+
+    if has_details {
+        table = table.push(detail_buttons);
+    }
+
+    table
+        .push(name_col)
+        .push(self_time_col)
+        .push(total_time_col)
+        .push(self_time_avg_col)
+        .push(total_time_avg_col)
+        .into()
+}
@@ -0,0 +1,46 @@
+use iced::{widget::{button, canvas, column, horizontal_rule, row, text}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::flame_chart::FlameChart;
+
+use crate::gui::flame_chart::{FlameChartProgram, AXIS_HEIGHT, ROW_HEIGHT};
+use crate::gui::{style::MONO, Message};
+
+// Extra space below the rows so that the tooltip of the lowest row fits
+const TOOLTIP_SPACE: f32 = 80.0;
+
+pub fn view<'a>(source: &'a str, chart: &'a Result<FlameChart, String>) -> Element<'a, Message> {
+
+    let top_row = row![
+        button(text("BACK").font(MONO))
+            .on_press(Message::BrowseFlameChartClosed),
+        text("Scroll to zoom, drag to pan, right click to reset").font(MONO).size(12),
+    ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+    let content: Element<'a, Message> = match chart {
+        Ok(chart) => {
+            let height = AXIS_HEIGHT + (chart.max_depth + 1) as f32 * ROW_HEIGHT + TOOLTIP_SPACE;
+
+            scrollable(
+                canvas(FlameChartProgram { chart })
+                    .width(iced::Length::Fill)
+                    .height(height)
+            )
+                .width(iced::Length::Fill)
+                .direction(Direction::Vertical(Scrollbar::new()))
+                .into()
+        }
+        Err(e) => text(e).font(MONO).size(12).into(),
+    };
+
+    column![
+        top_row,
+        horizontal_rule(2),
+        text(source).font(MONO).size(12),
+        content,
+    ]
+        .spacing(4)
+        .into()
+}
@@ -1,19 +1,27 @@
 use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
 use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
 
+use crate::processing::name_filter::{self, NameFilter};
 use crate::processing::{summary::FrontendOperationKey, AnalyisisResult};
 
-use crate::processing::summary::FrontendOperation;
-
-use crate::gui::{style::MONO, BrowsingFrontendSelectedOption, Message};
+use crate::gui::{style::MONO, BrowsingFrontendSelectedGrouping, BrowsingFrontendSelectedOption, Message};
 
 
 pub fn view<'a>(
     state: &'a AnalyisisResult,
     pane_state: BrowsingFrontendSelectedOption,
-    full_name_display: &'a Option<FrontendOperationKey>
+    grouping: BrowsingFrontendSelectedGrouping,
+    full_name_display: &'a Option<FrontendOperationKey>,
+    filter: Option<&'a NameFilter>,
 ) -> Element<'a, Message> {
 
+    if matches!(grouping, BrowsingFrontendSelectedGrouping::Templates) {
+        return match full_name_display {
+            Some(full_name_display) => template_details(state, full_name_display),
+            None => templates_table(state, pane_state, filter),
+        };
+    }
+
     if let Some(full_name_display) = full_name_display {
         let summary = state.summary.frontend_operation_summaries.get(full_name_display).unwrap();
 
@@ -59,13 +67,7 @@ pub fn view<'a>(
             text(&full_name_display.0).font(MONO).size(12),
         ].spacing(4).into()
     } else {
-    let top_row = row![
-        button(text("TOTAL").font(MONO))
-            .on_press(Message::BrowseFrontendTotalTimeClicked),
-        button(text("SELF").font(MONO))
-            .on_press(Message::BrowseFrontendSelfTimeClicked),
-    ]
-        .spacing(4);
+        let top_row = top_row();
 
         let limit = 100;
 
@@ -119,7 +121,7 @@ pub fn view<'a>(
             BrowsingFrontendSelectedOption::SelfTime => &state.summary.frontend_operation_largest_self_time_indices,
         };
 
-        for key in items_to_display.iter().take(limit) {
+        for key in name_filter::filtered(items_to_display, filter, |k| &k.0).take(limit) {
             name_col = name_col.push(
                 text(limit_string_name(&key.0)).font(MONO).size(12)
             );
@@ -132,13 +134,7 @@ pub fn view<'a>(
                     .on_press(Message::BrowseFrontendFullNameClicked(key.clone()))
             );
 
-            let type_text = match key.1 {
-                FrontendOperation::CodeGenFunction => "CF",
-                FrontendOperation::DebugType => "DT",
-                FrontendOperation::InstantiateClass => "IC",
-                FrontendOperation::InstantiateFunction => "IF",
-                FrontendOperation::ParseClass => "PC",
-            };
+            let type_text = key.1.short_name();
 
             type_col = type_col.push(
                 text(type_text).font(MONO).size(12)
@@ -201,6 +197,267 @@ pub fn view<'a>(
     }
 }
 
+fn top_row<'a>() -> Element<'a, Message> {
+    row![
+        button(text("OPS").font(MONO))
+            .on_press(Message::BrowseFrontendOperationsClicked),
+        button(text("TMPL").font(MONO))
+            .on_press(Message::BrowseFrontendTemplatesClicked),
+        text(" ").font(MONO),
+        button(text("TOTAL").font(MONO))
+            .on_press(Message::BrowseFrontendTotalTimeClicked),
+        button(text("SELF").font(MONO))
+            .on_press(Message::BrowseFrontendSelfTimeClicked),
+    ]
+        .spacing(4)
+        .into()
+}
+
+// Operations grouped by their template name, with the number of distinct instantiations
+fn templates_table<'a>(
+    state: &'a AnalyisisResult,
+    pane_state: BrowsingFrontendSelectedOption,
+    filter: Option<&'a NameFilter>,
+) -> Element<'a, Message> {
+    let limit = 100;
+
+    let mut num_occurences = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut num_instantiations = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Inst.").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut type_col = Column::new()
+        .spacing(6)
+        .push(text("Type").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut disp_buttons = Column::new()
+        .spacing(6)
+        .push(text(" ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Template").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Self [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_avg_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("AVG Total [ms]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let items_to_display = match pane_state {
+        BrowsingFrontendSelectedOption::TotalTime => &state.summary.template_group_largest_total_time_indices,
+        BrowsingFrontendSelectedOption::SelfTime => &state.summary.template_group_largest_self_time_indices,
+    };
+
+    for key in name_filter::filtered(items_to_display, filter, |k| &k.0).take(limit) {
+        let group = &state.summary.template_groups[key];
+
+        num_occurences = num_occurences.push(
+            text(group.num.to_string()).font(MONO).size(12)
+        );
+
+        num_instantiations = num_instantiations.push(
+            text(group.instantiations.len().to_string()).font(MONO).size(12)
+        );
+
+        disp_buttons = disp_buttons.push(
+            button(text("DISP").font(MONO).size(12))
+                .padding(0)
+                .on_press(Message::BrowseFrontendFullNameClicked(key.clone()))
+        );
+
+        type_col = type_col.push(
+            text(key.1.short_name()).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(
+            text(limit_string_name(&key.0)).font(MONO).size(12)
+        );
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", group.self_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", group.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        total_time_avg_col = total_time_avg_col.push(
+            text(format!("{:.2}", group.total_time_us as f64 * 1e-3 / group.num as f64)).font(MONO).size(12)
+        );
+    }
+
+    let table = row![
+        num_occurences,
+        num_instantiations,
+        disp_buttons,
+        type_col,
+        name_col,
+        self_time_col,
+        total_time_col,
+        total_time_avg_col,
+    ]
+        .spacing(12);
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        top_row(),
+        horizontal_rule(2),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
+
+// Totals of a template group and its most expensive instantiations
+fn template_details<'a>(
+    state: &'a AnalyisisResult,
+    key: &'a FrontendOperationKey,
+) -> Element<'a, Message> {
+    let limit = 20;
+
+    let group = &state.summary.template_groups[key];
+
+    let descriptions = column![
+        text("Instantiations").font(MONO).size(12),
+        text("#").font(MONO).size(12),
+        text("∑ Total [s]").font(MONO).size(12),
+        text("∑ Self [s]").font(MONO).size(12),
+        text("AVG Total [ms]").font(MONO).size(12),
+    ]
+        .spacing(6)
+        .align_x(alignment::Alignment::End);
+
+    let values = column![
+        text(group.instantiations.len().to_string()).font(MONO).size(12),
+        text(group.num.to_string()).font(MONO).size(12),
+        text(format!("{:.2}", group.total_time_us as f64 * 1e-6)).font(MONO).size(12),
+        text(format!("{:.2}", group.self_time_us as f64 * 1e-6)).font(MONO).size(12),
+        text(format!("{:.2}", group.total_time_us as f64 * 1e-3 / group.num as f64)).font(MONO).size(12),
+    ]
+        .spacing(6)
+        .align_x(alignment::Alignment::End);
+
+    let stats = row![
+        descriptions,
+        values,
+    ]
+        .spacing(12);
+
+    let buttons = row![
+        button(text("BACK").font(MONO).size(12))
+            .on_press(Message::BrowseFrontendFullNameClosed),
+        button(text("COPY").font(MONO).size(12))
+            .on_press(Message::CopyToClipboard(key.0.clone())),
+    ]
+        .spacing(12);
+
+    let mut num_occurences = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut disp_buttons = Column::new()
+        .spacing(6)
+        .push(text(" ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Instantiation").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Self [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("∑ Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    for name in group.instantiations.iter().take(limit) {
+        let operation_key = (name.clone(), key.1);
+        let summary = &state.summary.frontend_operation_summaries[&operation_key];
+
+        num_occurences = num_occurences.push(
+            text(summary.num.to_string()).font(MONO).size(12)
+        );
+
+        disp_buttons = disp_buttons.push(
+            button(text("DISP").font(MONO).size(12))
+                .padding(0)
+                .on_press(Message::BrowseFrontendInstantiationClicked(operation_key))
+        );
+
+        name_col = name_col.push(
+            text(limit_string_name(name)).font(MONO).size(12)
+        );
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", summary.self_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", summary.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+    }
+
+    let instantiations = row![
+        num_occurences,
+        disp_buttons,
+        name_col,
+        self_time_col,
+        total_time_col,
+    ]
+        .spacing(12);
+
+    let content = scrollable(instantiations)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        buttons,
+        horizontal_rule(2),
+        stats,
+        horizontal_rule(2),
+        text(format!("{} {}", key.1.short_name(), key.0)).font(MONO).size(12),
+        horizontal_rule(2),
+        content,
+    ]
+        .spacing(4)
+        .into()
+}
+
 // Fill the string with `...` in the misdle if it's too long
 fn limit_string_name(name: &str) -> String {
     const MAX_LEN: usize = 100;
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::summary::IncludeTreeNode;
+use crate::processing::AnalyisisResult;
+
+use crate::gui::{style::MONO, BrowsingIncludeTreeSelectedOption, Message};
+
+use super::limit_string_name;
+
+// At most this many children are listed under a single node, and this many rows overall
+const CHILDREN_LIMIT: usize = 100;
+const ROWS_LIMIT: usize = 1000;
+
+struct TreeColumns<'a> {
+    toggle_col: Column<'a, Message>,
+    total_time_col: Column<'a, Message>,
+    self_time_col: Column<'a, Message>,
+    num_col: Column<'a, Message>,
+    name_col: Column<'a, Message>,
+    rows: usize,
+}
+
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    pane_state: BrowsingIncludeTreeSelectedOption,
+    expanded: &'a BTreeSet<Vec<String>>,
+) -> Element<'a, Message> {
+
+    let top_row = row![
+        button(text("TUS").font(MONO))
+            .on_press(Message::BrowseIncludeTreeTranslationUnitsClicked),
+        button(text("AGGR").font(MONO))
+            .on_press(Message::BrowseIncludeTreeAggregatedClicked),
+    ]
+        .spacing(4);
+
+    if state.summary.translation_units.is_empty() {
+        return column![
+            top_row,
+            horizontal_rule(2),
+            text("The include hierarchy is only available when analyzing a build directory").font(MONO).size(12),
+        ]
+            .spacing(4)
+            .into();
+    }
+
+    let mut columns = TreeColumns {
+        toggle_col: Column::new()
+            .spacing(6)
+            .push(text(" ").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        total_time_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("∑ Total [s]").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        self_time_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("∑ Self [s]").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        num_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("#").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        name_col: Column::new()
+            .spacing(6)
+            .push(text("Name").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        rows: 0,
+    };
+
+    match pane_state {
+        BrowsingIncludeTreeSelectedOption::TranslationUnits => {
+            // The top level lists the translation units, each with its own include hierarchy
+            let mut translation_units: Vec<(&String, &IncludeTreeNode)> = state.summary.translation_units
+                .iter()
+                .map(|(name, file)| (name, &file.include_tree))
+                .collect();
+
+            translation_units.sort_by_key(|(_, node)| std::cmp::Reverse(node.total_time_us));
+
+            push_rows(&mut columns, expanded, &[], translation_units);
+        }
+        BrowsingIncludeTreeSelectedOption::Aggregated => {
+            push_rows(&mut columns, expanded, &[], state.summary.include_tree.sorted_children());
+        }
+    }
+
+    let table = row![
+        columns.toggle_col,
+        columns.total_time_col,
+        columns.self_time_col,
+        columns.num_col,
+        columns.name_col,
+    ]
+        .spacing(12);
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        top_row,
+        horizontal_rule(2),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
+
+// Add a row for every child, and recursively the rows of the expanded children below it
+fn push_rows<'a>(
+    columns: &mut TreeColumns<'a>,
+    expanded: &BTreeSet<Vec<String>>,
+    parent_path: &[String],
+    children: Vec<(&String, &IncludeTreeNode)>,
+) {
+    for (name, node) in children.into_iter().take(CHILDREN_LIMIT) {
+        if columns.rows >= ROWS_LIMIT {
+            return;
+        }
+        columns.rows += 1;
+
+        let mut path = parent_path.to_vec();
+        path.push(name.clone());
+
+        let is_expanded = expanded.contains(&path);
+
+        let toggle: Element<'a, Message> = if node.children.is_empty() {
+            text(" ").font(MONO).size(12).into()
+        } else {
+            button(text(if is_expanded { "-" } else { "+" }).font(MONO).size(12))
+                .padding(0)
+                .on_press(Message::BrowseIncludeTreeToggled(path.clone()))
+                .into()
+        };
+
+        columns.toggle_col = std::mem::replace(&mut columns.toggle_col, Column::new()).push(toggle);
+
+        columns.total_time_col = std::mem::replace(&mut columns.total_time_col, Column::new()).push(
+            text(format!("{:.2}", node.total_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.self_time_col = std::mem::replace(&mut columns.self_time_col, Column::new()).push(
+            text(format!("{:.2}", node.self_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.num_col = std::mem::replace(&mut columns.num_col, Column::new()).push(
+            text(node.num.to_string())
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.name_col = std::mem::replace(&mut columns.name_col, Column::new()).push(
+            text(format!("{}{}", "  ".repeat(parent_path.len()), limit_string_name(name)))
+                .font(MONO)
+                .size(12)
+        );
+
+        if is_expanded {
+            push_rows(columns, expanded, &path, node.sorted_children());
+        }
+    }
+}
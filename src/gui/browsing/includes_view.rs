@@ -1,12 +1,22 @@
 use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
 use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
 
+use crate::processing::name_filter::{self, NameFilter};
 use crate::processing::AnalyisisResult;
 
 use crate::gui::{style::MONO, BrowsingIncludesSelectedOption, Message};
 
 
-pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingIncludesSelectedOption) -> Element<'a, Message> {
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    pane_state: BrowsingIncludesSelectedOption,
+    includers_display: &'a Option<String>,
+    filter: Option<&'a NameFilter>,
+) -> Element<'a, Message> {
+
+    if let Some(include) = includers_display {
+        return includers_view(state, include);
+    }
 
     let top_row = row![
         button(text("TOTAL").font(MONO))
@@ -24,6 +34,11 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingIncludesSelected
         .push(text("#").font(MONO).size(12))
         .push(text("").font(MONO).size(4));
 
+    let mut disp_buttons = Column::new()
+        .spacing(6)
+        .push(text(" ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
     let mut sources_col = Column::new()
         .spacing(6)
         .push(text("Include Path").font(MONO).size(12))
@@ -58,11 +73,17 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingIncludesSelected
         BrowsingIncludesSelectedOption::SelfTime => &state.summary.frontend_file_largest_self_time_indices,
     };
 
-    for source in sources_to_display.iter().take(limit) {
+    for source in name_filter::filtered(sources_to_display, filter, |k| k).take(limit) {
         sources_col = sources_col.push(
             text(limit_string_name(source)).font(MONO).size(12)
         );
 
+        disp_buttons = disp_buttons.push(
+            button(text("TUS").font(MONO).size(12))
+                .padding(0)
+                .on_press(Message::BrowseIncludeIncludersClicked(source.clone()))
+        );
+
         let summary = state.summary.frontend_file_process_summaries.get(source).unwrap();
 
         self_time_col = self_time_col.push(
@@ -98,6 +119,7 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingIncludesSelected
 
     let table = row![
         num_includes_col,
+        disp_buttons,
         sources_col,
         self_time_col,
         total_time_col,
@@ -119,6 +141,111 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingIncludesSelected
         .into()
 }
 
+// Translation units which included `include`, the most expensive first
+fn includers_view<'a>(state: &'a AnalyisisResult, include: &'a str) -> Element<'a, Message> {
+    let limit = 100;
+
+    let include_summary = state.summary.frontend_file_process_summaries.get(include).unwrap();
+
+    let buttons = row![
+        button(text("BACK").font(MONO).size(12))
+            .on_press(Message::BrowseIncludeIncludersClosed),
+        button(text("COPY").font(MONO).size(12))
+            .on_press(Message::CopyToClipboard(include.to_string())),
+    ]
+        .spacing(12);
+
+    let description = if include_summary.translation_units.is_empty() {
+        "Translation units are only available when analyzing a build directory".to_string()
+    } else {
+        format!(
+            "Included by {} translation units (showing up to {})",
+            include_summary.translation_units.len(),
+            limit
+        )
+    };
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut self_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Self [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut num_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut target_col = Column::new()
+        .spacing(6)
+        .push(text("Target").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut source_col = Column::new()
+        .spacing(6)
+        .push(text("Source Path").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    for translation_unit in include_summary.translation_units.iter().take(limit) {
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", translation_unit.total_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", translation_unit.self_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        num_col = num_col.push(
+            text(translation_unit.num.to_string())
+                .font(MONO)
+                .size(12)
+        );
+
+        target_col = target_col.push(
+            text(&translation_unit.target_name).font(MONO).size(12)
+        );
+
+        source_col = source_col.push(
+            text(limit_string_name(&translation_unit.source)).font(MONO).size(12)
+        );
+    }
+
+    let table = row![
+        total_time_col,
+        self_time_col,
+        num_col,
+        target_col,
+        source_col,
+    ]
+        .spacing(12);
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        buttons,
+        horizontal_rule(2),
+        text(include).font(MONO).size(12),
+        text(description).font(MONO).size(12),
+        horizontal_rule(2),
+        content,
+    ]
+        .spacing(4)
+        .into()
+}
+
 // Fill the string with `...` in the misdle if it's too long
 fn limit_string_name(name: &str) -> String {
     const MAX_LEN: usize = 80;
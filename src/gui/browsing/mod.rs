@@ -1,6 +1,46 @@
 pub mod backend_view;
+pub mod comparison_view;
+pub mod events_view;
+pub mod flame_chart_view;
 pub mod frontend_view;
+pub mod include_tree_view;
 pub mod includes_view;
+pub mod namespaces_view;
 pub mod sources_view;
 pub mod summary_view;
-pub mod top_level_selector;
\ No newline at end of file
+pub mod critical_path_view;
+pub mod timeline_view;
+pub mod top_level_selector;
+pub mod translation_unit_view;
+pub mod variants_view;
+
+// Names longer than this many characters are shortened, keeping this many of their first characters
+const NAME_MAX_LEN: usize = 100;
+const NAME_FIRST_LETTER_COUNT: usize = 24;
+
+// Fill the string with `...` in the middle if it's too long
+pub(super) fn limit_string_name(name: &str) -> String {
+    let len = name.chars().count();
+
+    if len > NAME_MAX_LEN {
+
+        let letters_to_skip = len - NAME_MAX_LEN;
+
+        let mut result = String::new();
+
+        // take first letter in unicode compatible way
+        for c in name.chars().take(NAME_FIRST_LETTER_COUNT) {
+            result.push(c);
+        }
+
+        result.push_str("...");
+
+        for c in name.chars().skip(letters_to_skip) {
+            result.push(c);
+        }
+
+        result
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeSet;
+
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::namespace_tree::NamespaceNode;
+use crate::processing::AnalyisisResult;
+
+use crate::gui::{style::MONO, BrowsingNamespacesSelectedOption, Message};
+
+use super::limit_string_name;
+
+// At most this many children are listed under a single namespace, and this many rows overall
+const CHILDREN_LIMIT: usize = 100;
+const ROWS_LIMIT: usize = 1000;
+
+struct TreeColumns<'a> {
+    toggle_col: Column<'a, Message>,
+    total_time_col: Column<'a, Message>,
+    frontend_time_col: Column<'a, Message>,
+    backend_time_col: Column<'a, Message>,
+    percent_col: Column<'a, Message>,
+    num_col: Column<'a, Message>,
+    name_col: Column<'a, Message>,
+    rows: usize,
+}
+
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    pane_state: BrowsingNamespacesSelectedOption,
+    expanded: &'a BTreeSet<Vec<String>>,
+) -> Element<'a, Message> {
+
+    let top_row = row![
+        button(text("TOTAL").font(MONO))
+            .on_press(Message::BrowseNamespacesTotalClicked),
+        button(text("FRNT").font(MONO))
+            .on_press(Message::BrowseNamespacesFrontendClicked),
+        button(text("BCKN").font(MONO))
+            .on_press(Message::BrowseNamespacesBackendClicked),
+    ]
+        .spacing(4);
+
+    let root = &state.summary.namespace_tree;
+
+    let mut columns = TreeColumns {
+        toggle_col: Column::new()
+            .spacing(6)
+            .push(text(" ").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        total_time_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("∑ Total [s]").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        frontend_time_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("Frontend Self [s]").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        backend_time_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("Backend [s]").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        percent_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("[%]").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        num_col: Column::new()
+            .spacing(6)
+            .align_x(alignment::Alignment::End)
+            .push(text("#").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        name_col: Column::new()
+            .spacing(6)
+            .push(text("Namespace").font(MONO).size(12))
+            .push(text("").font(MONO).size(4)),
+        rows: 0,
+    };
+
+    push_rows(&mut columns, expanded, pane_state, root.total_time_us(), &[], root);
+
+    let table = row![
+        columns.toggle_col,
+        columns.total_time_col,
+        columns.frontend_time_col,
+        columns.backend_time_col,
+        columns.percent_col,
+        columns.num_col,
+        columns.name_col,
+    ]
+        .spacing(12);
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        top_row,
+        text("Self time of the frontend operations and time of the backend symbols, summed by their enclosing namespaces and classes").font(MONO).size(12),
+        horizontal_rule(2),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
+
+// Add a row for every child, and recursively the rows of the expanded children below it
+fn push_rows<'a>(
+    columns: &mut TreeColumns<'a>,
+    expanded: &BTreeSet<Vec<String>>,
+    pane_state: BrowsingNamespacesSelectedOption,
+    root_time_us: u64,
+    parent_path: &[String],
+    parent: &NamespaceNode,
+) {
+    for (name, node) in parent.sorted_children(|n| pane_state.time_us(n)).into_iter().take(CHILDREN_LIMIT) {
+        if columns.rows >= ROWS_LIMIT {
+            return;
+        }
+        columns.rows += 1;
+
+        let mut path = parent_path.to_vec();
+        path.push(name.clone());
+
+        let is_expanded = expanded.contains(&path);
+
+        let toggle: Element<'a, Message> = if node.children.is_empty() {
+            text(" ").font(MONO).size(12).into()
+        } else {
+            button(text(if is_expanded { "-" } else { "+" }).font(MONO).size(12))
+                .padding(0)
+                .on_press(Message::BrowseNamespacesToggled(path.clone()))
+                .into()
+        };
+
+        columns.toggle_col = std::mem::replace(&mut columns.toggle_col, Column::new()).push(toggle);
+
+        columns.total_time_col = std::mem::replace(&mut columns.total_time_col, Column::new()).push(
+            text(format!("{:.2}", node.total_time_us() as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.frontend_time_col = std::mem::replace(&mut columns.frontend_time_col, Column::new()).push(
+            text(format!("{:.2}", node.frontend_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.backend_time_col = std::mem::replace(&mut columns.backend_time_col, Column::new()).push(
+            text(format!("{:.2}", node.backend_time_us as f64 * 1e-6))
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.percent_col = std::mem::replace(&mut columns.percent_col, Column::new()).push(
+            text(format!("{:.1}", node.total_time_us() as f64 * 100.0 / root_time_us.max(1) as f64))
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.num_col = std::mem::replace(&mut columns.num_col, Column::new()).push(
+            text(node.num.to_string())
+                .font(MONO)
+                .size(12)
+        );
+
+        columns.name_col = std::mem::replace(&mut columns.name_col, Column::new()).push(
+            text(format!("{}{}", "  ".repeat(parent_path.len()), limit_string_name(name)))
+                .font(MONO)
+                .size(12)
+        );
+
+        if is_expanded {
+            push_rows(columns, expanded, pane_state, root_time_us, &path, node);
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
 use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
 
+use crate::processing::name_filter::{self, NameFilter};
 use crate::processing::AnalyisisResult;
 
 use crate::gui::{style::MONO, BrowsingSourcesSelectedOption, Message};
 
 
-pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingSourcesSelectedOption) -> Element<'a, Message> {
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    pane_state: BrowsingSourcesSelectedOption,
+    filter: Option<&'a NameFilter>,
+) -> Element<'a, Message> {
 
     let top_row = row![
         button(text("TOTAL").font(MONO))
@@ -15,14 +20,22 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingSourcesSelectedO
             .on_press(Message::BrowseSourcePaneFrontendTimeClicked),
         button(text("BACK").font(MONO))
             .on_press(Message::BrowseSourcePaneBackendTimeClicked),
+        text(" ").font(MONO),
+        button(text("FLAGS").font(MONO))
+            .on_press(Message::BrowseSourcePaneFlagGroupsClicked),
     ]
         .spacing(4);
 
     let limit = 100;
 
+    let mut disp_buttons = Column::new()
+        .spacing(6)
+        .push(text(" ").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
     let mut sources_col = Column::new()
         .spacing(6)
-        .push(text("Include Path").font(MONO).size(12))
+        .push(text("Source Path").font(MONO).size(12))
         .push(text("").font(MONO).size(4));
 
     let mut total_time_col = Column::new()
@@ -47,13 +60,39 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingSourcesSelectedO
         BrowsingSourcesSelectedOption::TotalTime => &state.summary.source_file_largest_total_time_indices,
         BrowsingSourcesSelectedOption::FrontendTime => &state.summary.source_file_largest_frontend_time_indices,
         BrowsingSourcesSelectedOption::BackendTime => &state.summary.source_file_largest_backend_time_indices,
+        BrowsingSourcesSelectedOption::FlagGroups => {
+            return column![
+                top_row,
+                horizontal_rule(2),
+                flag_groups_view(state),
+            ]
+                .spacing(4)
+                .into();
+        }
     };
 
-    for source in sources_to_display.iter().take(limit) {
+    for source in name_filter::filtered(sources_to_display, filter, |k| k).take(limit) {
+        // The real path of the source is only known from `compile_commands.json`
+        let source_path = state.summary.compile_commands
+            .get(source)
+            .map(|command| command.file.as_str())
+            .unwrap_or(source);
+
         sources_col = sources_col.push(
-            text(limit_string_name(source)).font(MONO).size(12)
+            text(limit_string_name(source_path)).font(MONO).size(12)
         );
 
+        // The details of a single translation unit are not stored in reports
+        if state.summary.translation_units.contains_key(source) {
+            disp_buttons = disp_buttons.push(
+                button(text("DISP").font(MONO).size(12))
+                    .padding(0)
+                    .on_press(Message::BrowseSourceTranslationUnitClicked(source.clone()))
+            );
+        } else {
+            disp_buttons = disp_buttons.push(text(" ").font(MONO).size(12));
+        }
+
         let summary = state.summary.source_file_process_summaries.get(source).unwrap();
 
         total_time_col = total_time_col.push(
@@ -76,6 +115,7 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingSourcesSelectedO
     }
 
     let table = row![
+        disp_buttons,
         sources_col,
         total_time_col,
         frontend_time_col,
@@ -96,6 +136,68 @@ pub fn view<'a>(state: &'a AnalyisisResult, pane_state: BrowsingSourcesSelectedO
         .into()
 }
 
+fn flag_groups_view(state: &AnalyisisResult) -> Element<'_, Message> {
+    let summary = &state.summary;
+
+    if summary.compile_commands.is_empty() {
+        return text("No compile_commands.json found next to CMakeFiles, enable CMAKE_EXPORT_COMPILE_COMMANDS to group by flags")
+            .font(MONO)
+            .size(12)
+            .into();
+    }
+
+    let limit = 100;
+
+    let mut num_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("#").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut total_time_col = Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text("Total [s]").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut example_col = Column::new()
+        .spacing(6)
+        .push(text("Most expensive").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    let mut flags_col = Column::new()
+        .spacing(6)
+        .push(text("Flags compared to most translation units").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    for group in summary.flag_groups.iter().take(limit) {
+        num_col = num_col.push(text(group.sources.len().to_string()).font(MONO).size(12));
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", group.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        example_col = example_col.push(
+            text(limit_string_name(group.sources.first().map(|s| s.as_str()).unwrap_or(""))).font(MONO).size(12)
+        );
+
+        flags_col = flags_col.push(text(limit_string_name(&group.description())).font(MONO).size(12));
+    }
+
+    let table = row![
+        num_col,
+        total_time_col,
+        example_col,
+        flags_col,
+    ]
+        .spacing(12);
+
+    scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() })
+        .into()
+}
+
 // Fill the string with `...` in the misdle if it's too long
 fn limit_string_name(name: &str) -> String {
     const MAX_LEN: usize = 80;
@@ -1,4 +1,5 @@
 use crate::gui::BrowsingSummarySelectedOption;
+use crate::processing::name_filter::{self, NameFilter};
 use crate::processing::AnalyisisResult;
 use crate::gui::trace_bar::TraceBar;
 
@@ -50,7 +51,11 @@ fn summary_row<'a, T: Into<String>>(label: &'static str, value: T) -> Row<'a, Me
         .spacing(8)
 }
 
-pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummarySelectedOption) -> Element<'a, Message> {
+pub fn view<'a>(
+    analysis: &'a AnalyisisResult,
+    selected_option: BrowsingSummarySelectedOption,
+    filter: Option<&'a NameFilter>,
+) -> Element<'a, Message> {
 
     let top_row = row![
         button(text("ALPHA").font(MONO))
@@ -69,13 +74,29 @@ pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummaryS
     let backend_single_events_secs = format!("{:.2}", analysis.summary.backend_duration_single_events_sec());
     let inferred_user_time_secs = format!("{:.2}", analysis.summary.inferred_used_time_secs());
 
+    let parallelism = &analysis.summary.parallelism;
+    let average_concurrency = format!("{:.2}", parallelism.average_concurrency);
+    let parallel_efficiency = format!("{:.1} % of {} cores", parallelism.parallel_efficiency * 100.0, parallelism.cores);
+    let sequential_stretches = parallelism.sequential_stretches
+        .iter()
+        .take(3)
+        .map(|stretch| format!("{:.2} s at {:.2} s", stretch.duration_us as f64 * 1e-6, stretch.start_us as f64 * 1e-6))
+        .collect::<Vec<_>>()
+        .join(", ");
+
     let summary_table = Column::new()
         .padding(8)
         .spacing(4);
 
-    let summary_table: Element<'a, Message> = summary_table
+    let mut summary_table = summary_table
         .push(summary_row("Selected path", &analysis.selected_path))
-        .push(summary_row("Resolved path", &analysis.resolved_cmake_files_path))
+        .push(summary_row("Resolved path", &analysis.resolved_cmake_files_path));
+
+    if let Some(report_path) = &analysis.report_path {
+        summary_table = summary_table.push(summary_row("Loaded report", report_path));
+    }
+
+    let mut summary_table = summary_table
         .push(summary_row("Total files", analysis.summary.total_files().to_string()))
         .push(summary_row("Total valid", analysis.summary.total_valid_files.to_string()))
         .push(summary_row("Total invalid", analysis.summary.total_invalid_files.to_string()))
@@ -84,7 +105,27 @@ pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummaryS
         .push(summary_row("Backend single events [s]", backend_single_events_secs))
 
         .push(summary_row("User time [s]", inferred_user_time_secs))
-        .into();
+        .push(summary_row("Average concurrency", average_concurrency))
+        .push(summary_row("Peak concurrency", parallelism.peak_concurrency.to_string()))
+        .push(summary_row("Parallel efficiency", parallel_efficiency))
+        .push(summary_row("Longest sequential", sequential_stretches))
+        .push(summary_row("Critical path [s]", format!("{:.2}", analysis.summary.critical_path.duration_us as f64 * 1e-6)));
+
+    // Only builds done with ninja have the steps without a trace file
+    let has_ninja_steps = !analysis.summary.ninja_steps.is_empty();
+
+    if has_ninja_steps {
+        let ninja = &analysis.summary.ninja;
+
+        summary_table = summary_table
+            .push(summary_row("Ninja wall time [s]", format!("{:.2}", ninja.wall_time_us as f64 * 1e-6)))
+            .push(summary_row("Not compiling [s]", format!("{:.2}", ninja.not_compiling_us as f64 * 1e-6)))
+            .push(summary_row("Compile steps [s]", format!("{:.2}", ninja.totals.compile_us as f64 * 1e-6)))
+            .push(summary_row("Link steps [s]", format!("{:.2}", ninja.totals.link_us as f64 * 1e-6)))
+            .push(summary_row("Other steps [s]", format!("{:.2}", ninja.totals.other_us as f64 * 1e-6)));
+    }
+
+    let summary_table: Element<'a, Message> = summary_table.into();
 
     let description_row_height = 32;
 
@@ -119,6 +160,16 @@ pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummaryS
             .wrapping(Wrapping::None)
         );
 
+    let mut target_steps_rows = ["Compile [s]", "Link [s]", "Other [s]"].map(|header| {
+        Column::new()
+            .align_x(Alignment::End)
+            .push(
+                Text::new(header)
+                .height(description_row_height)
+                .wrapping(Wrapping::None)
+            )
+    });
+
     let mut target_first_time_row = Column::new()
         .align_x(Alignment::End)
         .push(
@@ -151,7 +202,7 @@ pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummaryS
     };
 
     // for (target_name, target_summary) in &analysis.summary.target_summaries {
-    for target_name in target_keys {
+    for target_name in name_filter::filtered(target_keys, filter, |k| k) {
 
         let target_summary = &analysis.summary.target_summaries[target_name];
 
@@ -193,6 +244,23 @@ pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummaryS
             .height(row_height)
         );
 
+        let target_steps = analysis.summary.ninja.target_steps.get(target_name);
+
+        let target_steps_secs = match target_steps {
+            Some(steps) => [steps.compile_us, steps.link_us, steps.other_us].map(|us| format!("{:.2}", us as f64 * 1e-6)),
+            None => ["-", "-", "-"].map(|s| s.to_string()),
+        };
+
+        for (column, value) in target_steps_rows.iter_mut().zip(target_steps_secs) {
+            *column = std::mem::replace(column, Column::new()).push(
+                Text::new(value)
+                .font(MONO)
+                .size(VALUE_FONT_SIZE)
+                .wrapping(Wrapping::None)
+                .height(row_height)
+            );
+        }
+
         let relative_first_time = (target_summary.first_event_time - analysis.summary.first_event_time) as f64 * 1e-6;
         let relative_last_time = (target_summary.last_event_time - analysis.summary.first_event_time) as f64 * 1e-6;
 
@@ -231,16 +299,24 @@ pub fn view<'a>(analysis: &'a AnalyisisResult, selected_option: BrowsingSummaryS
         duration_graphics = duration_graphics.push(c);
     }
 
-    let target_table = row![
+    let mut target_table = row![
         target_names_row,
         target_files_row,
         target_frontend_row,
         target_backend_row,
-        target_first_time_row,
-        duration_graphics,
-        target_last_time_row
     ].spacing(12);
 
+    if has_ninja_steps {
+        for column in target_steps_rows {
+            target_table = target_table.push(column);
+        }
+    }
+
+    let target_table = target_table
+        .push(target_first_time_row)
+        .push(duration_graphics)
+        .push(target_last_time_row);
+
     let outer = column![
         top_row,
         horizontal_rule(2),
@@ -0,0 +1,38 @@
+use iced::{widget::{canvas, column, horizontal_rule, text}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::AnalyisisResult;
+
+use crate::gui::build_timeline::{self, BuildTimeline};
+use crate::gui::{style::MONO, Message};
+
+
+pub fn view(state: &AnalyisisResult) -> Element<'_, Message> {
+    let summary = &state.summary;
+
+    if summary.compile_jobs.is_empty() {
+        return text("No compile jobs, the timeline needs the start time of every translation unit").font(MONO).size(12).into();
+    }
+
+    let description = format!(
+        "{} compile jobs on {} lanes, the plot below shows how many were running at the same time",
+        summary.compile_jobs.len(),
+        summary.timeline.lane_count,
+    );
+
+    let content = scrollable(
+        canvas(BuildTimeline { summary })
+            .width(iced::Length::Fill)
+            .height(build_timeline::canvas_height(summary))
+    )
+        .width(iced::Length::Fill)
+        .direction(Direction::Vertical(Scrollbar::new()));
+
+    column![
+        text(description).font(MONO).size(12),
+        horizontal_rule(2),
+        content,
+    ]
+        .spacing(4)
+        .into()
+}
@@ -1,16 +1,27 @@
-use iced::{widget::{button, column, horizontal_rule, row, text}, Element};
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, text_input}, Element};
 
 use crate::gui::{AnalyzingFilesState, BrowsingPane, Message, style::MONO};
 
-use super::{summary_view, includes_view, sources_view, frontend_view, backend_view};
+use super::{summary_view, includes_view, include_tree_view, sources_view, translation_unit_view, flame_chart_view, frontend_view, backend_view, namespaces_view, events_view, timeline_view, critical_path_view, comparison_view, variants_view};
 
 pub fn view(state: & AnalyzingFilesState) -> Element<'_, Message> {
+    let filter = state.name_filter();
+
     let pane_content = match &state.browsing_pane {
-        BrowsingPane::Summary{ selected_option } => summary_view::view(&state.analysis, *selected_option),
-        BrowsingPane::Includes{ selected_option } => includes_view::view(&state.analysis, *selected_option),
-        BrowsingPane::Sources{ selected_option } => sources_view::view(&state.analysis, *selected_option),
-        BrowsingPane::Frontend{ selected_option, full_name_display } => frontend_view::view(&state.analysis, *selected_option, full_name_display),
-        BrowsingPane::Backend{ full_name_display } => backend_view::view(&state.analysis, full_name_display),
+        BrowsingPane::Summary{ selected_option } => summary_view::view(&state.analysis, *selected_option, filter),
+        BrowsingPane::Includes{ selected_option, includers_display } => includes_view::view(&state.analysis, *selected_option, includers_display, filter),
+        BrowsingPane::Sources{ selected_option } => sources_view::view(&state.analysis, *selected_option, filter),
+        BrowsingPane::TranslationUnit{ source, selected_table } => translation_unit_view::view(&state.analysis, source, *selected_table),
+        BrowsingPane::FlameChart{ source, chart } => flame_chart_view::view(source, chart),
+        BrowsingPane::IncludeTree{ selected_option, expanded } => include_tree_view::view(&state.analysis, *selected_option, expanded),
+        BrowsingPane::Frontend{ selected_option, selected_grouping, full_name_display } => frontend_view::view(&state.analysis, *selected_option, *selected_grouping, full_name_display, filter),
+        BrowsingPane::Backend{ selected_option, full_name_display } => backend_view::view(&state.analysis, *selected_option, full_name_display, filter),
+        BrowsingPane::Namespaces{ selected_option, expanded } => namespaces_view::view(&state.analysis, *selected_option, expanded),
+        BrowsingPane::Events{ selected_option, selected_name } => events_view::view(&state.analysis, *selected_option, selected_name, filter),
+        BrowsingPane::Timeline => timeline_view::view(&state.analysis),
+        BrowsingPane::CriticalPath{ selected_option } => critical_path_view::view(&state.analysis, *selected_option),
+        BrowsingPane::Comparison{ selected_table, selected_option } => comparison_view::view(state, *selected_table, *selected_option),
+        BrowsingPane::Variants{ selected_table, selected_option } => variants_view::view(state, *selected_table, *selected_option),
     };
 
     const SIDEBAR_WIDTH: u16 = 68;
@@ -23,6 +34,10 @@ pub fn view(state: & AnalyzingFilesState) -> Element<'_, Message> {
         button(text("REF").font(MONO))
             .width(SIDEBAR_WIDTH)
             .on_press(Message::BrowseRefreshClicked),
+        button(text("WTCH").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseWatchClicked)
+            .style(if state.watching { iced::widget::button::success } else { iced::widget::button::primary }),
         horizontal_rule(2),
         button(text("SMRY").font(MONO))
             .width(SIDEBAR_WIDTH)
@@ -33,6 +48,9 @@ pub fn view(state: & AnalyzingFilesState) -> Element<'_, Message> {
         button(text("SRCS").font(MONO))
             .width(SIDEBAR_WIDTH)
             .on_press(Message::BrowseTopLevelPaneSourceClicked),
+        button(text("TREE").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneIncludeTreeClicked),
         button(text("FRNT").font(MONO))
             .width(SIDEBAR_WIDTH)
             .on_press(Message::BrowseTopLevelPaneFrontendClicked),
@@ -40,11 +58,80 @@ pub fn view(state: & AnalyzingFilesState) -> Element<'_, Message> {
             .font(MONO))
             .width(SIDEBAR_WIDTH)
             .on_press(Message::BrowseTopLevelPaneBackendClicked),
+        button(text("NSPC").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneNamespacesClicked),
+        button(text("EVNT").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneEventsClicked),
+        button(text("TIME").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneTimelineClicked),
+        button(text("CRIT").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneCriticalPathClicked),
+        button(text("DIFF").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneComparisonClicked),
+        button(text("CONF").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseTopLevelPaneVariantsClicked),
+        horizontal_rule(2),
+        button(text("JSON").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseExportJsonClicked),
+        button(text("CSV").font(MONO))
+            .width(SIDEBAR_WIDTH)
+            .on_press(Message::BrowseExportCsvClicked),
     ]
         .padding(4)
         .spacing(4)
         .width(SIDEBAR_WIDTH);
 
+    // Substring or regular expression matched against the names listed in the pane
+    let pane_content: Element<'_, Message> = if state.browsing_pane.is_filterable() {
+        let (filter_text, regex, error) = match state.pane_filter() {
+            Some(filter) => (filter.text.as_str(), filter.regex, filter.error.as_deref()),
+            None => ("", false, None),
+        };
+
+        let mut filter_row = row![
+            text_input("Filter names, e.g. third_party/ or std::function", filter_text)
+                .on_input(Message::BrowseFilterChanged)
+                .font(MONO)
+                .size(12)
+                .width(480),
+            button(text("RGX").font(MONO).size(12))
+                .on_press(Message::BrowseFilterRegexClicked)
+                .style(if regex { iced::widget::button::success } else { iced::widget::button::primary }),
+        ]
+            .spacing(4)
+            .align_y(alignment::Alignment::Center);
+
+        if let Some(error) = error {
+            filter_row = filter_row.push(text(error.lines().last().unwrap_or_default().to_string()).font(MONO).size(12));
+        }
+
+        column![
+            filter_row,
+            pane_content,
+        ]
+            .spacing(4)
+            .into()
+    } else {
+        pane_content
+    };
+
+    let pane_content: Element<'_, Message> = match &state.notice {
+        Some(notice) => column![
+            text(notice).font(MONO).size(12),
+            pane_content,
+        ]
+            .spacing(4)
+            .into(),
+        None => pane_content,
+    };
+
     row![
         sidebar,
         pane_content,
@@ -0,0 +1,273 @@
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::processing::file_summary::FileSummary;
+use crate::processing::AnalyisisResult;
+
+use crate::gui::{style::MONO, BrowsingTranslationUnitSelectedTable, Message};
+
+use super::limit_string_name;
+
+
+pub fn view<'a>(
+    state: &'a AnalyisisResult,
+    source: &'a str,
+    selected_table: BrowsingTranslationUnitSelectedTable,
+) -> Element<'a, Message> {
+
+    let top_row = row![
+        button(text("BACK").font(MONO))
+            .on_press(Message::BrowseTranslationUnitClosed),
+        button(text("COPY").font(MONO))
+            .on_press(Message::CopyToClipboard(source.to_string())),
+        text(" ").font(MONO),
+        button(text("INCS").font(MONO))
+            .on_press(Message::BrowseTranslationUnitIncludesClicked),
+        button(text("FRNT").font(MONO))
+            .on_press(Message::BrowseTranslationUnitFrontendClicked),
+        button(text("BCKN").font(MONO))
+            .on_press(Message::BrowseTranslationUnitBackendClicked),
+        button(text("PASS").font(MONO))
+            .on_press(Message::BrowseTranslationUnitPassesClicked),
+        text(" ").font(MONO),
+        button(text("FLME").font(MONO))
+            .on_press(Message::BrowseTranslationUnitFlameChartClicked),
+        text(" ").font(MONO),
+        button(text("CMD").font(MONO))
+            .on_press_maybe(
+                state.summary.compile_commands
+                    .get(source)
+                    .map(|command| Message::CopyToClipboard(command.command.clone()))
+            ),
+    ]
+        .spacing(4);
+
+    let Some(file) = state.summary.translation_units.get(source) else {
+        return column![
+            top_row,
+            horizontal_rule(2),
+            text(format!("No details for {}", source)).font(MONO).size(12),
+        ]
+            .spacing(4)
+            .into();
+    };
+
+    let descriptions = column![
+        text("Target").font(MONO).size(12),
+        text("Total [s]").font(MONO).size(12),
+        text("Frontend [s]").font(MONO).size(12),
+        text("Backend [s]").font(MONO).size(12),
+        text("Uncovered [s]").font(MONO).size(12),
+        text("Includes").font(MONO).size(12),
+    ]
+        .spacing(6)
+        .align_x(alignment::Alignment::End);
+
+    let values = column![
+        text(&file.target_name).font(MONO).size(12),
+        text(format!("{:.2}", (file.frontend_duration_us + file.backend_duration_us) as f64 * 1e-6)).font(MONO).size(12),
+        text(format!("{:.2}", file.frontend_duration_us as f64 * 1e-6)).font(MONO).size(12),
+        text(format!("{:.2}", file.backend_duration_us as f64 * 1e-6)).font(MONO).size(12),
+        text(format!("{:.2}", file.uncovered_duration_us as f64 * 1e-6)).font(MONO).size(12),
+        text(file.includes.len().to_string()).font(MONO).size(12),
+    ]
+        .spacing(6);
+
+    // Only known when the build directory has a `compile_commands.json`
+    let command = state.summary.compile_commands.get(source);
+
+    let (descriptions, values) = match command {
+        Some(command) => (
+            descriptions
+                .push(text("Source file").font(MONO).size(12))
+                .push(text("Output").font(MONO).size(12))
+                .push(text("Command").font(MONO).size(12)),
+            values
+                .push(text(&command.file).font(MONO).size(12))
+                .push(text(&command.output).font(MONO).size(12))
+                .push(text(limit_string_name(&command.command)).font(MONO).size(12)),
+        ),
+        None => (descriptions, values),
+    };
+
+    let info = row![
+        descriptions,
+        values,
+    ]
+        .spacing(12);
+
+    let table = match selected_table {
+        BrowsingTranslationUnitSelectedTable::Includes => includes_table(file),
+        BrowsingTranslationUnitSelectedTable::Frontend => frontend_table(file),
+        BrowsingTranslationUnitSelectedTable::Backend => backend_table(file),
+        BrowsingTranslationUnitSelectedTable::Passes => passes_table(file),
+    };
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        top_row,
+        horizontal_rule(2),
+        text(source).font(MONO).size(12),
+        info,
+        horizontal_rule(2),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
+
+fn time_column<'a>(header: &'a str) -> Column<'a, Message> {
+    Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text(header).font(MONO).size(12))
+        .push(text("").font(MONO).size(4))
+}
+
+fn name_column<'a>(header: &'a str) -> Column<'a, Message> {
+    Column::new()
+        .spacing(6)
+        .push(text(header).font(MONO).size(12))
+        .push(text("").font(MONO).size(4))
+}
+
+fn includes_table(file: &FileSummary) -> Element<'_, Message> {
+    let limit = 100;
+
+    let mut num_col = time_column("#");
+    let mut self_time_col = time_column("Self [s]");
+    let mut total_time_col = time_column("Total [s]");
+    let mut name_col = name_column("Include Path");
+
+    let mut includes: Vec<_> = file.includes.iter().collect();
+    includes.sort_by_key(|(_, include)| std::cmp::Reverse(include.total_time_us));
+
+    for (name, include) in includes.into_iter().take(limit) {
+        num_col = num_col.push(text(include.num.to_string()).font(MONO).size(12));
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", include.self_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", include.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(text(limit_string_name(name)).font(MONO).size(12));
+    }
+
+    row![
+        num_col,
+        self_time_col,
+        total_time_col,
+        name_col,
+    ]
+        .spacing(12)
+        .into()
+}
+
+fn frontend_table(file: &FileSummary) -> Element<'_, Message> {
+    let limit = 100;
+
+    let mut num_col = time_column("#");
+    let mut type_col = name_column("Type");
+    let mut self_time_col = time_column("Self [s]");
+    let mut total_time_col = time_column("Total [s]");
+    let mut name_col = name_column("Name");
+
+    let mut operations: Vec<_> = file.frontend_operations.iter().collect();
+    operations.sort_by_key(|(_, operation)| std::cmp::Reverse(operation.total_time_us));
+
+    for (key, operation) in operations.into_iter().take(limit) {
+        num_col = num_col.push(text(operation.num.to_string()).font(MONO).size(12));
+
+        type_col = type_col.push(text(key.1.short_name()).font(MONO).size(12));
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", operation.self_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", operation.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(text(limit_string_name(&key.0)).font(MONO).size(12));
+    }
+
+    row![
+        num_col,
+        type_col,
+        self_time_col,
+        total_time_col,
+        name_col,
+    ]
+        .spacing(12)
+        .into()
+}
+
+fn backend_table(file: &FileSummary) -> Element<'_, Message> {
+    let limit = 100;
+
+    let mut num_col = time_column("#");
+    let mut total_time_col = time_column("Total [s]");
+    let mut name_col = name_column("Name");
+
+    let mut operations: Vec<_> = file.backend_operations.iter().collect();
+    operations.sort_by_key(|(_, operation)| std::cmp::Reverse(operation.total_time_us));
+
+    for (name, operation) in operations.into_iter().take(limit) {
+        num_col = num_col.push(text(operation.num.to_string()).font(MONO).size(12));
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", operation.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(text(limit_string_name(name)).font(MONO).size(12));
+    }
+
+    row![
+        num_col,
+        total_time_col,
+        name_col,
+    ]
+        .spacing(12)
+        .into()
+}
+
+fn passes_table(file: &FileSummary) -> Element<'_, Message> {
+    let limit = 100;
+
+    let mut num_col = time_column("#");
+    let mut self_time_col = time_column("Self [s]");
+    let mut total_time_col = time_column("Total [s]");
+    let mut name_col = name_column("Pass");
+
+    let mut passes: Vec<_> = file.passes.iter().collect();
+    passes.sort_by_key(|(_, pass)| std::cmp::Reverse(pass.self_time_us));
+
+    for (name, pass) in passes.into_iter().take(limit) {
+        num_col = num_col.push(text(pass.num.to_string()).font(MONO).size(12));
+
+        self_time_col = self_time_col.push(
+            text(format!("{:.2}", pass.self_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        total_time_col = total_time_col.push(
+            text(format!("{:.2}", pass.total_time_us as f64 * 1e-6)).font(MONO).size(12)
+        );
+
+        name_col = name_col.push(text(limit_string_name(name)).font(MONO).size(12));
+    }
+
+    row![
+        num_col,
+        self_time_col,
+        total_time_col,
+        name_col,
+    ]
+        .spacing(12)
+        .into()
+}
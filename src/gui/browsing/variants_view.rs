@@ -0,0 +1,172 @@
+use iced::{alignment, widget::{button, column, horizontal_rule, row, text, Column, Row}, Element};
+use iced::widget::{scrollable, scrollable::Direction, scrollable::Scrollbar};
+
+use crate::model::BuildVariant;
+use crate::processing::variant_comparison::VariantTable;
+
+use crate::gui::{style::MONO, AnalyzingFilesState, BrowsingComparisonSelectedTable, BrowsingVariantsSelectedOption, Message};
+
+use super::limit_string_name;
+
+
+pub fn view<'a>(
+    state: &'a AnalyzingFilesState,
+    selected_table: BrowsingComparisonSelectedTable,
+    selected_option: BrowsingVariantsSelectedOption,
+) -> Element<'a, Message> {
+
+    let analyze_row = match &state.variants_loading {
+        Some(loading) => row![
+            button(text("CANCEL").font(MONO))
+                .on_press(Message::BrowseVariantsCancelClicked)
+                .style(iced::widget::button::danger),
+            text(format!(
+                "Analyzing {}: {}/{} files",
+                loading.build_variant.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+                loading.progress.files_processed(),
+                loading.progress.files_enumerated,
+            )).font(MONO).size(12),
+        ],
+        None => row![
+            button(text("ANALYZE").font(MONO))
+                .on_press(Message::BrowseVariantsAnalyzeClicked),
+            text("Analyze every configuration of the build directory, the first one is the reference of the ratios").font(MONO).size(12),
+        ],
+    }
+        .spacing(8)
+        .align_y(alignment::Alignment::Center);
+
+    let top_row = row![
+        button(text("TRGT").font(MONO))
+            .on_press(Message::BrowseVariantsTargetsClicked),
+        button(text("INCS").font(MONO))
+            .on_press(Message::BrowseVariantsIncludesClicked),
+        button(text("SRCS").font(MONO))
+            .on_press(Message::BrowseVariantsSourcesClicked),
+        button(text("FRNT").font(MONO))
+            .on_press(Message::BrowseVariantsFrontendClicked),
+        button(text("BCKN").font(MONO))
+            .on_press(Message::BrowseVariantsBackendClicked),
+        text(" ").font(MONO),
+        button(text("TIME").font(MONO))
+            .on_press(Message::BrowseVariantsTotalTimeClicked),
+        button(text("RATIO").font(MONO))
+            .on_press(Message::BrowseVariantsRatioClicked),
+    ]
+        .spacing(4);
+
+    let Some(comparison) = &state.variant_comparison else {
+        return column![
+            analyze_row,
+            horizontal_rule(2),
+            text("No configurations analyzed yet").font(MONO).size(12),
+        ]
+            .spacing(4)
+            .into();
+    };
+
+    let build_variants = &comparison.build_variants;
+    let by_ratio = matches!(selected_option, BrowsingVariantsSelectedOption::Ratio);
+
+    let totals = comparison.totals
+        .iter()
+        .zip(build_variants)
+        .map(|(totals, build_variant)| {
+            format!(
+                "{}: {} files, frontend {:.2} s, backend {:.2} s",
+                build_variant,
+                totals.total_files,
+                totals.frontend_sec,
+                totals.backend_sec,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    let table = match selected_table {
+        BrowsingComparisonSelectedTable::Targets => table(build_variants, &comparison.targets, |k| k.clone(), by_ratio),
+        BrowsingComparisonSelectedTable::Includes => table(build_variants, &comparison.includes, |k| k.clone(), by_ratio),
+        BrowsingComparisonSelectedTable::Sources => table(build_variants, &comparison.sources, |k| k.clone(), by_ratio),
+        BrowsingComparisonSelectedTable::Frontend => table(
+            build_variants,
+            &comparison.frontend_operations,
+            |k| format!("{} {}", k.1.short_name(), limit_string_name(&k.0)),
+            by_ratio
+        ),
+        BrowsingComparisonSelectedTable::Backend => table(build_variants, &comparison.backend_operations, |k| limit_string_name(k), by_ratio),
+    };
+
+    let content = scrollable(table)
+        .width(iced::Length::Fill)
+        .direction(Direction::Both { vertical: Scrollbar::new(), horizontal: Scrollbar::new() });
+
+    column![
+        analyze_row,
+        text(totals).font(MONO).size(12),
+        top_row,
+        horizontal_rule(2),
+        content
+    ]
+        .spacing(4)
+        .into()
+}
+
+fn header_column<'a>(header: String) -> Column<'a, Message> {
+    Column::new()
+        .spacing(6)
+        .align_x(alignment::Alignment::End)
+        .push(text(header).font(MONO).size(12))
+        .push(text("").font(MONO).size(4))
+}
+
+fn table<'a, K>(
+    build_variants: &[BuildVariant],
+    variant_table: &'a VariantTable<K>,
+    name: impl Fn(&K) -> String,
+    by_ratio: bool,
+) -> Element<'a, Message> {
+    let limit = 100;
+
+    let mut time_cols: Vec<Column<'a, Message>> = build_variants
+        .iter()
+        .map(|build_variant| header_column(format!("{} [s]", build_variant)))
+        .collect();
+
+    let mut ratio_cols: Vec<Column<'a, Message>> = build_variants
+        .iter()
+        .skip(1)
+        .map(|build_variant| header_column(format!("{}/{}", build_variant, build_variants[0])))
+        .collect();
+
+    let mut name_col = Column::new()
+        .spacing(6)
+        .push(text("Name").font(MONO).size(12))
+        .push(text("").font(MONO).size(4));
+
+    for entry in variant_table.ordered(by_ratio).take(limit) {
+        for (col, time_us) in time_cols.iter_mut().zip(entry.times_us.iter()) {
+            *col = std::mem::replace(col, Column::new()).push(
+                text(time_us.map(|us| format!("{:.2}", us as f64 * 1e-6)).unwrap_or("-".to_string()))
+                    .font(MONO)
+                    .size(12)
+            );
+        }
+
+        for (index, col) in ratio_cols.iter_mut().enumerate() {
+            *col = std::mem::replace(col, Column::new()).push(
+                text(entry.ratio(index + 1).map(|r| format!("{:.2}", r)).unwrap_or("-".to_string()))
+                    .font(MONO)
+                    .size(12)
+            );
+        }
+
+        name_col = name_col.push(
+            text(name(&entry.key)).font(MONO).size(12)
+        );
+    }
+
+    Row::with_children(time_cols.into_iter().chain(ratio_cols).map(Element::from))
+        .push(name_col)
+        .spacing(12)
+        .into()
+}
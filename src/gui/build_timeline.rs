@@ -0,0 +1,145 @@
+use iced::{mouse, widget::canvas, Renderer, Theme};
+use iced::widget::canvas::event::{self, Event};
+
+use crate::processing::summary::{CompileJob, Summary};
+
+use super::{style::MONO, tooltip};
+
+pub const LANE_HEIGHT: f32 = 8.0;
+pub const PLOT_HEIGHT: f32 = 120.0;
+const PLOT_GAP: f32 = 12.0;
+
+pub fn canvas_height(summary: &Summary) -> f32 {
+    summary.timeline.lane_count as f32 * LANE_HEIGHT + PLOT_GAP + PLOT_HEIGHT
+}
+
+// Every compile job on its lane, with the number of running jobs plotted below the lanes
+pub struct BuildTimeline<'a> {
+    pub summary: &'a Summary,
+}
+
+impl BuildTimeline<'_> {
+    fn to_x(&self, bounds: iced::Rectangle, time_us: u64) -> f32 {
+        time_us as f32 / self.summary.timeline.duration_us.max(1) as f32 * bounds.width
+    }
+
+    fn relative_us(&self, time: u128) -> u64 {
+        time.saturating_sub(self.summary.first_event_time) as u64
+    }
+
+    fn job_at(&self, bounds: iced::Rectangle, position: iced::Point) -> Option<&CompileJob> {
+        let lane = (position.y / LANE_HEIGHT) as usize;
+
+        if lane >= self.summary.timeline.lane_count {
+            return None;
+        }
+
+        self.summary.compile_jobs
+            .iter()
+            .zip(self.summary.timeline.lanes.iter())
+            .find(|(job, job_lane)| {
+                **job_lane == lane
+                    && self.to_x(bounds, self.relative_us(job.start_time)) <= position.x
+                    && position.x < self.to_x(bounds, self.relative_us(job.end_time())).max(self.to_x(bounds, self.relative_us(job.start_time)) + 1.0)
+            })
+            .map(|(job, _)| job)
+    }
+}
+
+impl<Message> canvas::Program<Message> for BuildTimeline<'_> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: Event,
+        bounds: iced::Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<Message>) {
+        // Redraw for the hover tooltip
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) if cursor.is_over(bounds) => (event::Status::Captured, None),
+            _ => (event::Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: iced::Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        let palette = theme.extended_palette();
+        let timeline = &self.summary.timeline;
+
+        let hovered = cursor.position_in(bounds).and_then(|p| self.job_at(bounds, p));
+
+        for (job, lane) in self.summary.compile_jobs.iter().zip(timeline.lanes.iter()) {
+            let x0 = self.to_x(bounds, self.relative_us(job.start_time));
+            let x1 = self.to_x(bounds, self.relative_us(job.end_time()));
+
+            let bar = canvas::Path::rectangle(
+                iced::Point::new(x0, *lane as f32 * LANE_HEIGHT),
+                iced::Size::new((x1 - x0).max(1.0), LANE_HEIGHT - 1.0),
+            );
+
+            let color = if hovered.is_some_and(|h| std::ptr::eq(h, job)) {
+                palette.success.strong.color
+            } else {
+                palette.primary.strong.color
+            };
+
+            frame.fill(&bar, color);
+        }
+
+        // Concurrency plot, scaled so that the peak fills the plot
+        let plot_top = timeline.lane_count as f32 * LANE_HEIGHT + PLOT_GAP;
+        let plot_bottom = plot_top + PLOT_HEIGHT;
+        let peak = timeline.peak_concurrency().max(1) as f32;
+
+        let background = canvas::Path::rectangle(iced::Point::new(0.0, plot_top), iced::Size::new(bounds.width, PLOT_HEIGHT));
+        frame.fill(&background, palette.background.weak.color);
+
+        for (i, (time, count)) in timeline.concurrency.iter().enumerate() {
+            let next_time = timeline.concurrency.get(i + 1).map(|(t, _)| *t).unwrap_or(timeline.duration_us);
+
+            let x0 = self.to_x(bounds, *time);
+            let x1 = self.to_x(bounds, next_time);
+            let height = *count as f32 / peak * PLOT_HEIGHT;
+
+            let step = canvas::Path::rectangle(
+                iced::Point::new(x0, plot_bottom - height),
+                iced::Size::new((x1 - x0).max(0.5), height),
+            );
+
+            frame.fill(&step, palette.secondary.strong.color);
+        }
+
+        frame.fill_text(canvas::Text {
+            content: format!("Peak {} jobs, {:.1} s", timeline.peak_concurrency(), timeline.duration_us as f64 * 1e-6),
+            position: iced::Point::new(2.0, plot_top + 2.0),
+            color: palette.background.weak.text,
+            size: 11.0.into(),
+            font: MONO,
+            ..Default::default()
+        });
+
+        // Tooltip of the hovered job, kept inside of the canvas
+        if let (Some(position), Some(job)) = (cursor.position_in(bounds), hovered) {
+            let lines = [
+                job.source.clone(),
+                format!("Target:   {}", job.target_name),
+                format!("Start:    {:.2} s", self.relative_us(job.start_time) as f64 * 1e-6),
+                format!("Duration: {:.2} s", job.duration_us as f64 * 1e-6),
+            ];
+
+            tooltip::draw(&mut frame, theme, bounds, position, &lines);
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
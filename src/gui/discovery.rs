@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use iced::futures::channel::oneshot;
+use iced::Task;
+
+use super::{persistance, Message};
+
+// How long the path input has to stay unchanged before the build directory is searched
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(300);
+
+// Emits `BrowseInputSettled` for `path` once the debounce interval passed
+pub fn settle(path: String) -> Task<Message> {
+    let (sender, receiver) = oneshot::channel();
+
+    std::thread::spawn(move || {
+        std::thread::sleep(DEBOUNCE_INTERVAL);
+        let _ = sender.send(path);
+    });
+
+    Task::perform(receiver, |path| Message::BrowseInputSettled(path.unwrap_or_default()))
+}
+
+// Search `path` for the build variants on a background thread, it walks every `.dir` directory.
+// The result is delivered by the message `on_discovered` makes.
+pub fn discover(path: String, on_discovered: fn(String, Vec<persistance::DiscoveredBuildVariant>) -> Message) -> Task<Message> {
+    let (sender, receiver) = oneshot::channel::<(String, Vec<persistance::DiscoveredBuildVariant>)>();
+
+    std::thread::spawn(move || {
+        let discovered = crate::processing::discover_build_variants(&path);
+        let _ = sender.send((path, discovered));
+    });
+
+    Task::perform(receiver, move |result| {
+        // An empty path never matches the input, so a stopped thread is ignored
+        let (path, discovered) = result.unwrap_or_default();
+        on_discovered(path, discovered)
+    })
+}
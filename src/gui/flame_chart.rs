@@ -0,0 +1,267 @@
+use iced::{mouse, widget::canvas, Renderer, Theme};
+use iced::widget::canvas::event::{self, Event};
+
+use crate::processing::flame_chart::{FlameChart, FlameChartEvent};
+
+use super::{style::MONO, tooltip};
+
+pub const AXIS_HEIGHT: f32 = 16.0;
+pub const ROW_HEIGHT: f32 = 18.0;
+
+// Approximate width of a character of the labels, used to cut them to the width of the event
+const CHAR_WIDTH: f32 = 7.0;
+
+// Smallest visible time range when zooming in
+const MIN_SPAN_US: f64 = 10.0;
+
+pub struct FlameChartProgram<'a> {
+    pub chart: &'a FlameChart,
+}
+
+// Visible time range and the state of a drag, kept by the canvas between frames
+#[derive(Default)]
+pub struct FlameChartState {
+    // Start and length of the visible range in microseconds, `None` shows the whole trace
+    view: Option<(f64, f64)>,
+    // Cursor x and visible range start when the drag started
+    drag_start: Option<(f32, f64)>,
+}
+
+impl FlameChartProgram<'_> {
+    fn visible_range(&self, state: &FlameChartState) -> (f64, f64) {
+        state.view.unwrap_or((0.0, self.chart.duration_us.max(1) as f64))
+    }
+
+    // Keep the range inside of the trace
+    fn clamp_range(&self, start: f64, span: f64) -> (f64, f64) {
+        let duration = self.chart.duration_us.max(1) as f64;
+        let span = span.clamp(MIN_SPAN_US.min(duration), duration);
+        let start = start.clamp(0.0, duration - span);
+        (start, span)
+    }
+
+    fn event_at(&self, state: &FlameChartState, bounds: iced::Rectangle, position: iced::Point) -> Option<&FlameChartEvent> {
+        if position.y < AXIS_HEIGHT {
+            return None;
+        }
+
+        let (start, span) = self.visible_range(state);
+        let depth = ((position.y - AXIS_HEIGHT) / ROW_HEIGHT) as usize;
+        let time = start + position.x as f64 / bounds.width as f64 * span;
+
+        self.chart.events
+            .iter()
+            .find(|e| e.depth == depth && e.start_us as f64 <= time && time < (e.start_us + e.duration_us) as f64)
+    }
+}
+
+fn event_color(theme: &Theme, name: &str) -> iced::Color {
+    let palette = theme.extended_palette();
+
+    match name {
+        "ExecuteCompiler" | "Frontend" | "Backend" => palette.background.strong.color,
+        "Source" => palette.primary.strong.color,
+        "InstantiateFunction" | "InstantiateClass" | "ParseClass" | "DebugType" | "CodeGenFunction"
+            | "PerformPendingInstantiations" | "ParseTemplate" => palette.success.strong.color,
+        _ => palette.secondary.strong.color,
+    }
+}
+
+fn event_label(event: &FlameChartEvent) -> String {
+    match &event.detail {
+        Some(detail) => format!("{} {}", event.name, detail),
+        None => event.name.clone(),
+    }
+}
+
+// Cut the label to the given number of characters
+fn cut_label(label: &str, max_chars: usize) -> String {
+    if label.chars().count() > max_chars {
+        let mut result: String = label.chars().take(max_chars.saturating_sub(1)).collect();
+        result.push('…');
+        result
+    } else {
+        label.to_string()
+    }
+}
+
+impl<Message> canvas::Program<Message> for FlameChartProgram<'_> {
+    type State = FlameChartState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: Event,
+        bounds: iced::Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<Message>) {
+        let Event::Mouse(mouse_event) = event else {
+            return (event::Status::Ignored, None);
+        };
+
+        let (start, span) = self.visible_range(state);
+
+        match mouse_event {
+            // Zoom around the time under the cursor
+            mouse::Event::WheelScrolled { delta } => {
+                let Some(position) = cursor.position_in(bounds) else {
+                    return (event::Status::Ignored, None);
+                };
+
+                let lines = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y,
+                    mouse::ScrollDelta::Pixels { y, .. } => y / 50.0,
+                };
+
+                let fraction = (position.x / bounds.width) as f64;
+                let time = start + fraction * span;
+                let new_span = span * 0.8f64.powf(lines as f64);
+
+                state.view = Some(self.clamp_range(time - fraction * new_span, new_span));
+
+                (event::Status::Captured, None)
+            }
+            mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                let Some(position) = cursor.position_in(bounds) else {
+                    return (event::Status::Ignored, None);
+                };
+
+                state.drag_start = Some((position.x, start));
+
+                (event::Status::Captured, None)
+            }
+            // Reset the zoom
+            mouse::Event::ButtonPressed(mouse::Button::Right) => {
+                if !cursor.is_over(bounds) {
+                    return (event::Status::Ignored, None);
+                }
+
+                state.view = None;
+
+                (event::Status::Captured, None)
+            }
+            mouse::Event::ButtonReleased(mouse::Button::Left) if state.drag_start.is_some() => {
+                state.drag_start = None;
+
+                (event::Status::Captured, None)
+            }
+            mouse::Event::CursorMoved { position } => {
+                if let Some((drag_x, drag_view_start)) = state.drag_start {
+                    let offset = (position.x - bounds.x - drag_x) as f64 / bounds.width as f64 * span;
+                    state.view = Some(self.clamp_range(drag_view_start - offset, span));
+
+                    return (event::Status::Captured, None);
+                }
+
+                // Redraw for the hover tooltip
+                if cursor.is_over(bounds) {
+                    return (event::Status::Captured, None);
+                }
+
+                (event::Status::Ignored, None)
+            }
+            _ => (event::Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: iced::Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        let palette = theme.extended_palette();
+        let (start, span) = self.visible_range(state);
+
+        let to_x = |time_us: f64| ((time_us - start) / span * bounds.width as f64) as f32;
+
+        // Time axis with the visible range
+        frame.fill_text(canvas::Text {
+            content: format!("{:.1} ms", start * 1e-3),
+            position: iced::Point::new(2.0, 0.0),
+            color: palette.background.base.text,
+            size: 11.0.into(),
+            font: MONO,
+            ..Default::default()
+        });
+
+        frame.fill_text(canvas::Text {
+            content: format!("{:.1} ms", (start + span) * 1e-3),
+            position: iced::Point::new(bounds.width - 2.0, 0.0),
+            color: palette.background.base.text,
+            size: 11.0.into(),
+            font: MONO,
+            horizontal_alignment: iced::alignment::Horizontal::Right,
+            ..Default::default()
+        });
+
+        for event in self.chart.events.iter() {
+            let x0 = to_x(event.start_us as f64);
+            let x1 = to_x((event.start_us + event.duration_us) as f64);
+
+            // Outside of the visible range or too small to see
+            if x1 < 0.0 || x0 > bounds.width || x1 - x0 < 0.5 {
+                continue;
+            }
+
+            let x0 = x0.max(0.0);
+            let x1 = x1.min(bounds.width);
+            let y = AXIS_HEIGHT + event.depth as f32 * ROW_HEIGHT;
+
+            let rectangle = canvas::Path::rectangle(
+                iced::Point::new(x0, y),
+                iced::Size::new(x1 - x0, ROW_HEIGHT - 2.0),
+            );
+
+            frame.fill(&rectangle, event_color(theme, &event.name));
+
+            let max_chars = ((x1 - x0 - 4.0) / CHAR_WIDTH) as usize;
+
+            if max_chars >= 3 {
+                frame.fill_text(canvas::Text {
+                    content: cut_label(&event_label(event), max_chars),
+                    position: iced::Point::new(x0 + 2.0, y + 2.0),
+                    color: palette.background.base.text,
+                    size: 11.0.into(),
+                    font: MONO,
+                    ..Default::default()
+                });
+            }
+        }
+
+        // Tooltip of the hovered event, kept inside of the canvas
+        if let Some(position) = cursor.position_in(bounds) {
+            if let Some(event) = self.event_at(state, bounds, position) {
+                let lines = [
+                    event.name.clone(),
+                    cut_label(event.detail.as_deref().unwrap_or(""), 120),
+                    format!("Duration: {:.2} ms", event.duration_us as f64 * 1e-3),
+                    format!("Start:    {:.2} ms", event.start_us as f64 * 1e-3),
+                ];
+
+                tooltip::draw(&mut frame, theme, bounds, position, &lines);
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: iced::Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.drag_start.is_some() {
+            mouse::Interaction::Grabbing
+        } else if cursor.is_over(bounds) {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
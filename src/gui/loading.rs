@@ -0,0 +1,253 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use iced::futures::channel::mpsc;
+use iced::widget::{button, column, text};
+use iced::{Element, Task};
+
+use crate::processing::{AnalyisisResult, AnalysisError, AnalysisProgress, FileCache};
+
+use super::{persistance, style::MONO, ComparisonState, Message};
+
+// How often the progress is sent to the UI while the analysis is running
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct LoadingFilesState {
+    pub path: String,
+    pub build_variant: persistance::BuildVariant,
+    pub progress: AnalysisProgress,
+    pub started: Instant,
+    cancel: Arc<AtomicBool>,
+    handle: iced::task::Handle,
+    // Baseline to compare against once the analysis is done, kept across a refresh
+    pub baseline: Option<ComparisonState>,
+}
+
+impl LoadingFilesState {
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        self.handle.abort();
+    }
+}
+
+// Result of a finished background job. Messages must be `Clone`, so the result is passed in a shared slot
+// and taken out exactly once by the update.
+pub struct Outcome<T>(Arc<Mutex<Option<T>>>);
+
+impl<T> Clone for Outcome<T> {
+    fn clone(&self) -> Self {
+        Outcome(self.0.clone())
+    }
+}
+
+impl<T> std::fmt::Debug for Outcome<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Outcome")
+    }
+}
+
+impl<T> Outcome<T> {
+    pub fn new(result: T) -> Self {
+        Outcome(Arc::new(Mutex::new(Some(result))))
+    }
+
+    pub fn take(&self) -> Option<T> {
+        self.0.lock().ok()?.take()
+    }
+}
+
+pub type AnalysisOutcome = Outcome<Result<AnalyisisResult, AnalysisError>>;
+
+// Configurations of a build directory, with the analyses of all of them but the already analyzed one
+pub struct LoadedVariants {
+    pub build_variants: Vec<persistance::BuildVariant>,
+    pub others: Vec<(persistance::BuildVariant, AnalyisisResult)>,
+}
+
+pub type VariantsOutcome = Outcome<Result<LoadedVariants, AnalysisError>>;
+
+// Analysis running in the background while browsing, see `start_baseline` and `start_variants`
+pub struct BackgroundLoadingState {
+    pub build_variant: Option<persistance::BuildVariant>,
+    pub progress: AnalysisProgress,
+    cancel: Arc<AtomicBool>,
+    handle: iced::task::Handle,
+}
+
+impl BackgroundLoadingState {
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        self.handle.abort();
+    }
+}
+
+// Start analysing `path` on a background thread. The returned task emits `AnalysisProgressed` until the
+// analysis is done, then a single `AnalysisFinished`. Unchanged files in `file_cache` are not parsed again.
+pub fn start(
+    path: String,
+    build_variant: persistance::BuildVariant,
+    file_cache: FileCache,
+    baseline: Option<ComparisonState>,
+) -> (LoadingFilesState, Task<Message>) {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::unbounded();
+
+    {
+        let path = path.clone();
+        let build_variant = build_variant.clone();
+        let cancel = cancel.clone();
+
+        std::thread::spawn(move || {
+            let result = load_with_progress(&path, &build_variant, file_cache, &cancel, |progress| {
+                let _ = sender.unbounded_send(Message::AnalysisProgressed(progress));
+            });
+
+            let _ = sender.unbounded_send(Message::AnalysisFinished(AnalysisOutcome::new(result)));
+        });
+    }
+
+    let (task, handle) = Task::run(receiver, |message| message).abortable();
+
+    let state = LoadingFilesState {
+        path,
+        build_variant,
+        progress: AnalysisProgress::default(),
+        started: Instant::now(),
+        cancel,
+        handle,
+        baseline,
+    };
+
+    (state, task)
+}
+
+// Start analysing the baseline `path` to compare against on a background thread. The returned task emits
+// `BaselineProgressed` until the analysis is done, then a single `BaselineLoaded`.
+pub fn start_baseline(path: String, build_variant: persistance::BuildVariant) -> (BackgroundLoadingState, Task<Message>) {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::unbounded();
+
+    {
+        let build_variant = build_variant.clone();
+        let cancel = cancel.clone();
+
+        std::thread::spawn(move || {
+            let result = load_with_progress(&path, &build_variant, FileCache::default(), &cancel, |progress| {
+                let _ = sender.unbounded_send(Message::BaselineProgressed(progress));
+            });
+
+            let _ = sender.unbounded_send(Message::BaselineLoaded(AnalysisOutcome::new(result)));
+        });
+    }
+
+    let (task, handle) = Task::run(receiver, |message| message).abortable();
+
+    let state = BackgroundLoadingState {
+        build_variant: Some(build_variant),
+        progress: AnalysisProgress::default(),
+        cancel,
+        handle,
+    };
+
+    (state, task)
+}
+
+// Start analysing every configuration of the build directory `path` but `analyzed` on a background thread.
+// The returned task emits `VariantsProgressed` for each of them, then a single `VariantsAnalyzed`.
+pub fn start_variants(path: String, analyzed: persistance::BuildVariant) -> (BackgroundLoadingState, Task<Message>) {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::unbounded();
+
+    {
+        let cancel = cancel.clone();
+
+        std::thread::spawn(move || {
+            let result = (|| {
+                let build_variants = crate::processing::variant_comparison::configurations(&path);
+
+                if build_variants.len() < 2 {
+                    return Err(AnalysisError::Other("the build directory has less than two configurations".to_string()));
+                }
+
+                let mut others = Vec::new();
+
+                for build_variant in build_variants.iter().filter(|v| **v != analyzed) {
+                    let other = load_with_progress(&path, build_variant, FileCache::default(), &cancel, |progress| {
+                        let _ = sender.unbounded_send(Message::VariantsProgressed(build_variant.clone(), progress));
+                    })?;
+                    others.push((build_variant.clone(), other));
+                }
+
+                Ok(LoadedVariants { build_variants, others })
+            })();
+
+            let _ = sender.unbounded_send(Message::VariantsAnalyzed(VariantsOutcome::new(result)));
+        });
+    }
+
+    let (task, handle) = Task::run(receiver, |message| message).abortable();
+
+    let state = BackgroundLoadingState {
+        build_variant: None,
+        progress: AnalysisProgress::default(),
+        cancel,
+        handle,
+    };
+
+    (state, task)
+}
+
+// Analyze `path` on a scoped thread, calling `on_progress` periodically until it is done
+fn load_with_progress(
+    path: &str,
+    build_variant: &persistance::BuildVariant,
+    file_cache: FileCache,
+    cancel: &AtomicBool,
+    on_progress: impl Fn(AnalysisProgress),
+) -> Result<AnalyisisResult, AnalysisError> {
+    let progress = Mutex::new(AnalysisProgress::default());
+
+    std::thread::scope(|scope| {
+        let analysis = scope.spawn(|| {
+            crate::processing::load_path_with_progress(path, build_variant, file_cache, cancel, &|p| {
+                if let Ok(mut progress) = progress.lock() {
+                    // Workers report out of order, keep the furthest one
+                    if p.files_processed() >= progress.files_processed() {
+                        *progress = p;
+                    }
+                }
+            })
+        });
+
+        while !analysis.is_finished() {
+            if let Ok(progress) = progress.lock() {
+                on_progress(*progress);
+            }
+            std::thread::sleep(PROGRESS_INTERVAL);
+        }
+
+        analysis
+            .join()
+            .unwrap_or_else(|_| Err(AnalysisError::Other("Analysis thread panicked".to_string())))
+    })
+}
+
+pub fn view(state: &LoadingFilesState) -> Element<'_, Message> {
+    let progress = &state.progress;
+
+    column![
+        text(format!("Analyzing {} ({})", state.path, state.build_variant)).font(MONO),
+        text(format!("Files enumerated: {}", progress.files_enumerated)).font(MONO).size(12),
+        text(format!("Files parsed:     {}", progress.files_parsed)).font(MONO).size(12),
+        text(format!("Files reused:     {}", progress.files_reused)).font(MONO).size(12),
+        text(format!("Invalid files:    {}", progress.invalid_files)).font(MONO).size(12),
+        text(format!("Elapsed:          {:.1} s", state.started.elapsed().as_secs_f64())).font(MONO).size(12),
+        button(text("CANCEL").font(MONO))
+            .on_press(Message::AnalysisCancelClicked)
+            .style(iced::widget::button::danger),
+    ]
+        .spacing(6)
+        .padding(10)
+        .into()
+}
@@ -1,17 +1,32 @@
 mod browsing;
+mod build_timeline;
+mod discovery;
+mod flame_chart;
+mod loading;
 mod persistance;
 mod style;
+mod tooltip;
 mod trace_bar;
+mod watch;
+
+use std::collections::{BTreeSet, HashMap};
+use std::mem::Discriminant;
 
 use iced::widget::{button, combo_box, text_input, Row};
 use iced::widget::{Column, Text};
-use iced::{Element, Task};
+use iced::{Element, Subscription, Task};
 
+use loading::{AnalysisOutcome, BackgroundLoadingState, LoadedVariants, LoadingFilesState, VariantsOutcome};
 use persistance::Persistance;
 use tracing::{event, Level};
 
+use crate::processing::comparison::Comparison;
+use crate::processing::flame_chart::FlameChart;
+use crate::processing::name_filter::{self, NameFilter};
+use crate::processing::namespace_tree::NamespaceNode;
 use crate::processing::summary::FrontendOperationKey;
-use crate::processing::AnalyisisResult;
+use crate::processing::variant_comparison::VariantComparison;
+use crate::processing::{AnalyisisResult, AnalysisProgress, FileCache};
 
 
 pub struct App {
@@ -27,15 +42,88 @@ pub struct AppCrossStateCache {
 pub enum AppState {
     LookingForFiles {
         current_path: String,
-        build_variants: combo_box::State<persistance::BuildVariant>,
+        // Build variants found in `current_path`, with their trace counts
+        build_variants: combo_box::State<persistance::DiscoveredBuildVariant>,
         build_variant: persistance::BuildVariant,
     },
-    AnalyzingFiles(AnalyzingFilesState),
+    Loading(Box<LoadingFilesState>),
+    AnalyzingFiles(Box<AnalyzingFilesState>),
 }
 
 pub struct AnalyzingFilesState {
     analysis: AnalyisisResult,
     browsing_pane: BrowsingPane,
+    // Result of the last export, displayed above the pane
+    notice: Option<String>,
+    baseline_input: String,
+    comparison: Option<ComparisonState>,
+    // The baseline is being analyzed for `comparison`
+    baseline_loading: Option<BackgroundLoadingState>,
+    // Every configuration of the build directory analyzed side by side, on demand
+    variant_comparison: Option<VariantComparison>,
+    // The other configurations are being analyzed for `variant_comparison`
+    variants_loading: Option<BackgroundLoadingState>,
+    // Live watch mode, the build directory is analyzed again periodically while set
+    watching: bool,
+    // Id of the running watch re-analysis, its file cache is taken out of `analysis` meanwhile
+    watch_in_flight: Option<u64>,
+    // Filter text of every pane kind, kept while browsing other panes
+    filters: HashMap<Discriminant<BrowsingPane>, PaneFilter>,
+}
+
+impl AnalyzingFilesState {
+    // Filter of the current pane, if it has one
+    fn pane_filter(&self) -> Option<&PaneFilter> {
+        if !self.browsing_pane.is_filterable() {
+            return None;
+        }
+
+        self.filters.get(&std::mem::discriminant(&self.browsing_pane))
+    }
+
+    fn name_filter(&self) -> Option<&NameFilter> {
+        self.pane_filter().and_then(|filter| filter.name_filter.as_ref())
+    }
+}
+
+#[derive(Default)]
+pub struct PaneFilter {
+    text: String,
+    regex: bool,
+    // Compiled from `text`, None when it is empty or an invalid regular expression
+    name_filter: Option<NameFilter>,
+    error: Option<String>,
+}
+
+impl PaneFilter {
+    fn update(&mut self) {
+        match NameFilter::new(&self.text, self.regex) {
+            Ok(name_filter) => {
+                self.name_filter = name_filter;
+                self.error = None;
+            }
+            Err(error) => {
+                self.name_filter = None;
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+pub struct ComparisonState {
+    baseline: AnalyisisResult,
+    comparison: Comparison,
+}
+
+impl ComparisonState {
+    fn new(baseline: AnalyisisResult, analysis: &AnalyisisResult) -> Self {
+        let comparison = crate::processing::comparison::compare(&baseline.summary, &analysis.summary);
+
+        ComparisonState {
+            baseline,
+            comparison,
+        }
+    }
 }
 
 pub enum BrowsingPane {
@@ -44,16 +132,72 @@ pub enum BrowsingPane {
     },
     Includes {
         selected_option: BrowsingIncludesSelectedOption,
+        // Include whose translation units are listed instead of the table
+        includers_display: Option<String>,
     },
     Sources {
         selected_option: BrowsingSourcesSelectedOption,
     },
+    TranslationUnit {
+        source: String,
+        selected_table: BrowsingTranslationUnitSelectedTable,
+    },
+    FlameChart {
+        source: String,
+        // Loaded from the trace file when the pane is opened
+        chart: Result<FlameChart, String>,
+    },
+    IncludeTree {
+        selected_option: BrowsingIncludeTreeSelectedOption,
+        // Paths from the top level to every expanded node
+        expanded: BTreeSet<Vec<String>>,
+    },
     Frontend {
         selected_option: BrowsingFrontendSelectedOption,
+        selected_grouping: BrowsingFrontendSelectedGrouping,
+        // Operation, or template group when grouped by templates, whose details are displayed
         full_name_display: Option<FrontendOperationKey>,
     },
     Backend {
+        selected_option: BrowsingBackendSelectedOption,
         full_name_display: Option<String>,
+    },
+    Namespaces {
+        selected_option: BrowsingNamespacesSelectedOption,
+        // Paths from the top level to every expanded namespace
+        expanded: BTreeSet<Vec<String>>,
+    },
+    Events {
+        selected_option: BrowsingEventsSelectedOption,
+        // Event name whose details are listed instead of the names
+        selected_name: Option<String>,
+    },
+    Timeline,
+    CriticalPath {
+        selected_option: BrowsingCriticalPathSelectedOption,
+    },
+    Comparison {
+        selected_table: BrowsingComparisonSelectedTable,
+        selected_option: BrowsingComparisonSelectedOption,
+    },
+    Variants {
+        selected_table: BrowsingComparisonSelectedTable,
+        selected_option: BrowsingVariantsSelectedOption,
+    },
+}
+
+impl BrowsingPane {
+    // Panes listing named items, which get a filter text box
+    fn is_filterable(&self) -> bool {
+        matches!(
+            self,
+            BrowsingPane::Summary { .. }
+                | BrowsingPane::Includes { .. }
+                | BrowsingPane::Sources { .. }
+                | BrowsingPane::Frontend { .. }
+                | BrowsingPane::Backend { .. }
+                | BrowsingPane::Events { .. }
+        )
     }
 }
 
@@ -76,6 +220,22 @@ pub enum BrowsingSourcesSelectedOption {
     TotalTime,
     FrontendTime,
     BackendTime,
+    // Translation units grouped by their compile flags instead of the list of sources
+    FlagGroups,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingTranslationUnitSelectedTable {
+    Includes,
+    Frontend,
+    Backend,
+    Passes,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingIncludeTreeSelectedOption {
+    TranslationUnits,
+    Aggregated,
 }
 
 #[derive(Copy, Clone)]
@@ -84,13 +244,88 @@ pub enum BrowsingFrontendSelectedOption {
     SelfTime,
 }
 
+#[derive(Copy, Clone)]
+pub enum BrowsingFrontendSelectedGrouping {
+    Operations,
+    // Operations of the same template merged, whatever the template arguments are
+    Templates,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingBackendSelectedOption {
+    // Optimized functions by their demangled symbol
+    Symbols,
+    // LLVM passes by their name
+    PassTotalTime,
+    PassSelfTime,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingNamespacesSelectedOption {
+    Total,
+    Frontend,
+    Backend,
+}
+
+impl BrowsingNamespacesSelectedOption {
+    // Time by which the namespaces are sorted
+    pub fn time_us(self, node: &NamespaceNode) -> u64 {
+        match self {
+            BrowsingNamespacesSelectedOption::Total => node.total_time_us(),
+            BrowsingNamespacesSelectedOption::Frontend => node.frontend_time_us,
+            BrowsingNamespacesSelectedOption::Backend => node.backend_time_us,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingEventsSelectedOption {
+    TotalTime,
+    SelfTime,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingCriticalPathSelectedOption {
+    Chain,
+    Contribution,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingComparisonSelectedTable {
+    Targets,
+    Includes,
+    Sources,
+    Frontend,
+    Backend,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingComparisonSelectedOption {
+    Regressions,
+    Improvements,
+}
+
+#[derive(Copy, Clone)]
+pub enum BrowsingVariantsSelectedOption {
+    TotalTime,
+    Ratio,
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     BrowseClicked,
     BrowseInputChanged(String),
+    BrowseInputSettled(String),
+    BuildVariantsDiscovered(String, Vec<persistance::DiscoveredBuildVariant>),
     BrowseLastItemOpen(String),
+    LastItemVariantsDiscovered(String, Vec<persistance::DiscoveredBuildVariant>),
     BrowserRemoveLastItem(String),
-    BrowseSelectedBuildVariant(persistance::BuildVariant),
+    BrowseSelectedBuildVariant(persistance::DiscoveredBuildVariant),
+
+    // Background analysis
+    AnalysisProgressed(AnalysisProgress),
+    AnalysisFinished(AnalysisOutcome),
+    AnalysisCancelClicked,
 
     // Browsing top level pane
     BrowseRefreshClicked,
@@ -98,8 +333,28 @@ pub enum Message {
     BrowseTopLevelPaneSummaryClicked,
     BrowseTopLevelPaneIncludeClicked,
     BrowseTopLevelPaneSourceClicked,
+    BrowseTopLevelPaneIncludeTreeClicked,
     BrowseTopLevelPaneFrontendClicked,
     BrowseTopLevelPaneBackendClicked,
+    BrowseTopLevelPaneNamespacesClicked,
+    BrowseTopLevelPaneEventsClicked,
+    BrowseTopLevelPaneTimelineClicked,
+    BrowseTopLevelPaneCriticalPathClicked,
+    BrowseTopLevelPaneComparisonClicked,
+    BrowseTopLevelPaneVariantsClicked,
+    BrowseExportJsonClicked,
+    BrowseExportCsvClicked,
+    BrowseWatchClicked,
+    BrowseFilterChanged(String),
+    BrowseFilterRegexClicked,
+
+    // Critical path pane
+    BrowseCriticalPathPaneChainClicked,
+    BrowseCriticalPathPaneContributionClicked,
+
+    // Live watch mode
+    WatchTick,
+    WatchAnalysisFinished(u64, AnalysisOutcome),
 
     // Browsing Summary Pane
     BrowseSummaryPaneAlphaClicked,
@@ -110,22 +365,88 @@ pub enum Message {
     // Browsing Include Pane
     BrowseIncludePaneTotalTimeClicked,
     BrowseIncludePaneSelfTimeClicked,
+    BrowseIncludeIncludersClicked(String),
+    BrowseIncludeIncludersClosed,
 
     // Browsing Sources Pane
     BrowseSourcePaneTotalTimeClicked,
     BrowseSourcePaneFrontendTimeClicked,
     BrowseSourcePaneBackendTimeClicked,
+    BrowseSourcePaneFlagGroupsClicked,
+    BrowseSourceTranslationUnitClicked(String),
+
+    // Translation Unit Pane
+    BrowseTranslationUnitClosed,
+    BrowseTranslationUnitIncludesClicked,
+    BrowseTranslationUnitFrontendClicked,
+    BrowseTranslationUnitBackendClicked,
+    BrowseTranslationUnitPassesClicked,
+    BrowseTranslationUnitFlameChartClicked,
+
+    // Flame Chart Pane
+    BrowseFlameChartClosed,
+
+    // Include Tree Pane
+    BrowseIncludeTreeTranslationUnitsClicked,
+    BrowseIncludeTreeAggregatedClicked,
+    BrowseIncludeTreeToggled(Vec<String>),
 
     // Frontend Pane
     BrowseFrontendTotalTimeClicked,
     BrowseFrontendSelfTimeClicked,
+    BrowseFrontendOperationsClicked,
+    BrowseFrontendTemplatesClicked,
     BrowseFrontendFullNameClicked(FrontendOperationKey),
     BrowseFrontendFullNameClosed,
+    // Display a concrete operation from the details of a template group
+    BrowseFrontendInstantiationClicked(FrontendOperationKey),
 
     // Backend Pane
+    BrowseBackendSymbolsClicked,
+    BrowseBackendPassTotalTimeClicked,
+    BrowseBackendPassSelfTimeClicked,
     BrowseBackendFullNameClicked(String),
     BrowseBackendFullNameClosed,
 
+    // Namespaces Pane
+    BrowseNamespacesTotalClicked,
+    BrowseNamespacesFrontendClicked,
+    BrowseNamespacesBackendClicked,
+    BrowseNamespacesToggled(Vec<String>),
+
+    // Events Pane
+    BrowseEventsTotalTimeClicked,
+    BrowseEventsSelfTimeClicked,
+    BrowseEventsNameClicked(String),
+    BrowseEventsNameClosed,
+
+    // Comparison Pane
+    BrowseComparisonBaselineInputChanged(String),
+    BrowseComparisonLoadClicked,
+    BrowseComparisonCancelClicked,
+    BaselineProgressed(AnalysisProgress),
+    BaselineLoaded(AnalysisOutcome),
+    BrowseComparisonTargetsClicked,
+    BrowseComparisonIncludesClicked,
+    BrowseComparisonSourcesClicked,
+    BrowseComparisonFrontendClicked,
+    BrowseComparisonBackendClicked,
+    BrowseComparisonRegressionsClicked,
+    BrowseComparisonImprovementsClicked,
+
+    // Configurations Pane
+    BrowseVariantsAnalyzeClicked,
+    BrowseVariantsCancelClicked,
+    VariantsProgressed(persistance::BuildVariant, AnalysisProgress),
+    VariantsAnalyzed(VariantsOutcome),
+    BrowseVariantsTargetsClicked,
+    BrowseVariantsIncludesClicked,
+    BrowseVariantsSourcesClicked,
+    BrowseVariantsFrontendClicked,
+    BrowseVariantsBackendClicked,
+    BrowseVariantsTotalTimeClicked,
+    BrowseVariantsRatioClicked,
+
     // Used for all text inputs which do nothing.
     // This enables copy-pasting from an input field, but not connecting it to any action.
     #[allow(dead_code)]
@@ -135,6 +456,169 @@ pub enum Message {
     CopyToClipboard(String),
 }
 
+// CSV file name and content of the table displayed in the current pane, in the selected order
+fn current_pane_csv(state: &AnalyzingFilesState) -> Option<(&'static str, String)> {
+    use crate::processing::csv_export;
+
+    let summary = &state.analysis.summary;
+    let filter = state.name_filter();
+
+    match &state.browsing_pane {
+        BrowsingPane::Summary { selected_option } => {
+            let order = match selected_option {
+                BrowsingSummarySelectedOption::Alpha => &summary.target_summaries_alpha_order,
+                BrowsingSummarySelectedOption::StartTime => &summary.target_summaries_first_event_indices,
+                BrowsingSummarySelectedOption::EndTime => &summary.target_summaries_last_event_indices,
+                BrowsingSummarySelectedOption::Duration => &summary.target_summaries_largest_duration_indices,
+            };
+            let order: Vec<_> = name_filter::filtered(order, filter, |k| k).cloned().collect();
+            Some(("targets.csv", csv_export::targets_csv(summary, &order)))
+        }
+        BrowsingPane::Includes { selected_option: _, includers_display: Some(include) } => {
+            Some(("include_translation_units.csv", csv_export::including_translation_units_csv(summary, include)))
+        }
+        BrowsingPane::Includes { selected_option, includers_display: None } => {
+            let order = match selected_option {
+                BrowsingIncludesSelectedOption::TotalTime => &summary.frontend_file_largest_time_indices,
+                BrowsingIncludesSelectedOption::SelfTime => &summary.frontend_file_largest_self_time_indices,
+            };
+            let order: Vec<_> = name_filter::filtered(order, filter, |k| k).cloned().collect();
+            Some(("includes.csv", csv_export::includes_csv(summary, &order)))
+        }
+        BrowsingPane::Sources { selected_option } => {
+            let order = match selected_option {
+                BrowsingSourcesSelectedOption::TotalTime => &summary.source_file_largest_total_time_indices,
+                BrowsingSourcesSelectedOption::FrontendTime => &summary.source_file_largest_frontend_time_indices,
+                BrowsingSourcesSelectedOption::BackendTime => &summary.source_file_largest_backend_time_indices,
+                BrowsingSourcesSelectedOption::FlagGroups => return Some(("flag_groups.csv", csv_export::flag_groups_csv(summary))),
+            };
+            let order: Vec<_> = name_filter::filtered(order, filter, |k| k).cloned().collect();
+            Some(("sources.csv", csv_export::sources_csv(summary, &order)))
+        }
+        BrowsingPane::FlameChart { source: _, chart: _ } => None,
+        BrowsingPane::Namespaces { selected_option, expanded: _ } => {
+            Some(("namespaces.csv", csv_export::namespace_tree_csv(&summary.namespace_tree, |n| selected_option.time_us(n))))
+        }
+        BrowsingPane::Events { selected_option, selected_name } => {
+            match (selected_option, selected_name) {
+                (selected_option, None) => {
+                    let order = match selected_option {
+                        BrowsingEventsSelectedOption::TotalTime => &summary.event_name_largest_total_time_indices,
+                        BrowsingEventsSelectedOption::SelfTime => &summary.event_name_largest_self_time_indices,
+                    };
+                    let order: Vec<_> = name_filter::filtered(order, filter, |k| k).cloned().collect();
+                    Some(("event_names.csv", csv_export::event_names_csv(summary, &order)))
+                }
+                (selected_option, Some(name)) => {
+                    let order = match selected_option {
+                        BrowsingEventsSelectedOption::TotalTime => &summary.event_largest_total_time_indices,
+                        BrowsingEventsSelectedOption::SelfTime => &summary.event_largest_self_time_indices,
+                    };
+                    // The details are filtered, the name is already selected
+                    let order: Vec<_> = name_filter::filtered(order, filter, |k| &k.1).filter(|key| key.0 == *name).cloned().collect();
+                    Some(("events.csv", csv_export::events_csv(summary, &order)))
+                }
+            }
+        }
+        BrowsingPane::Timeline => Some(("compile_jobs.csv", csv_export::compile_jobs_csv(summary))),
+        BrowsingPane::CriticalPath { selected_option } => {
+            let order: Vec<usize> = match selected_option {
+                BrowsingCriticalPathSelectedOption::Chain => (0..summary.critical_path.steps.len()).collect(),
+                BrowsingCriticalPathSelectedOption::Contribution => summary.critical_path.largest_contribution_indices.clone(),
+            };
+            Some(("critical_path.csv", csv_export::critical_path_csv(summary, &order)))
+        }
+        BrowsingPane::TranslationUnit { source, selected_table: _ } => {
+            let file = summary.translation_units.get(source)?;
+            Some(("translation_unit.csv", csv_export::translation_unit_csv(file)))
+        }
+        BrowsingPane::IncludeTree { selected_option, expanded: _ } => {
+            let csv = match selected_option {
+                BrowsingIncludeTreeSelectedOption::TranslationUnits => csv_export::include_tree_csv(
+                    summary.translation_units.iter().map(|(name, file)| (name, &file.include_tree))
+                ),
+                BrowsingIncludeTreeSelectedOption::Aggregated => csv_export::include_tree_csv(summary.include_tree.sorted_children()),
+            };
+            Some(("include_tree.csv", csv))
+        }
+        BrowsingPane::Frontend { selected_option, selected_grouping: BrowsingFrontendSelectedGrouping::Operations, full_name_display: _ } => {
+            let order = match selected_option {
+                BrowsingFrontendSelectedOption::TotalTime => &summary.frontend_operation_largest_total_time_indices,
+                BrowsingFrontendSelectedOption::SelfTime => &summary.frontend_operation_largest_self_time_indices,
+            };
+            let order: Vec<_> = name_filter::filtered(order, filter, |k| &k.0).cloned().collect();
+            Some(("frontend.csv", csv_export::frontend_operations_csv(summary, &order)))
+        }
+        BrowsingPane::Frontend { selected_option, selected_grouping: BrowsingFrontendSelectedGrouping::Templates, full_name_display: _ } => {
+            let order = match selected_option {
+                BrowsingFrontendSelectedOption::TotalTime => &summary.template_group_largest_total_time_indices,
+                BrowsingFrontendSelectedOption::SelfTime => &summary.template_group_largest_self_time_indices,
+            };
+            let order: Vec<_> = name_filter::filtered(order, filter, |k| &k.0).cloned().collect();
+            Some(("templates.csv", csv_export::template_groups_csv(summary, &order)))
+        }
+        BrowsingPane::Backend { selected_option, full_name_display: _ } => {
+            let (file_name, order) = match selected_option {
+                BrowsingBackendSelectedOption::Symbols => ("backend.csv", &summary.backend_operation_largest_total_time_indices),
+                BrowsingBackendSelectedOption::PassTotalTime => ("passes.csv", &summary.pass_largest_total_time_indices),
+                BrowsingBackendSelectedOption::PassSelfTime => ("passes.csv", &summary.pass_largest_self_time_indices),
+            };
+            let order: Vec<_> = name_filter::filtered(order, filter, |k| k).cloned().collect();
+            match selected_option {
+                BrowsingBackendSelectedOption::Symbols => Some((file_name, csv_export::backend_operations_csv(summary, &order))),
+                _ => Some((file_name, csv_export::passes_csv(summary, &order))),
+            }
+        }
+        BrowsingPane::Comparison { selected_table, selected_option } => {
+            let comparison = &state.comparison.as_ref()?.comparison;
+            let improvements_first = matches!(selected_option, BrowsingComparisonSelectedOption::Improvements);
+
+            let csv = match selected_table {
+                BrowsingComparisonSelectedTable::Targets => ("targets_diff.csv", csv_export::comparison_csv(&comparison.targets, |k| k.clone(), improvements_first)),
+                BrowsingComparisonSelectedTable::Includes => ("includes_diff.csv", csv_export::comparison_csv(&comparison.includes, |k| k.clone(), improvements_first)),
+                BrowsingComparisonSelectedTable::Sources => ("sources_diff.csv", csv_export::comparison_csv(&comparison.sources, |k| k.clone(), improvements_first)),
+                BrowsingComparisonSelectedTable::Frontend => ("frontend_diff.csv", csv_export::comparison_csv(&comparison.frontend_operations, |k| format!("{} {}", k.1.short_name(), k.0), improvements_first)),
+                BrowsingComparisonSelectedTable::Backend => ("backend_diff.csv", csv_export::comparison_csv(&comparison.backend_operations, |k| k.clone(), improvements_first)),
+            };
+
+            Some(csv)
+        }
+        BrowsingPane::Variants { selected_table, selected_option } => {
+            let comparison = state.variant_comparison.as_ref()?;
+            let build_variants = &comparison.build_variants;
+            let by_ratio = matches!(selected_option, BrowsingVariantsSelectedOption::Ratio);
+
+            let csv = match selected_table {
+                BrowsingComparisonSelectedTable::Targets => ("targets_variants.csv", csv_export::variant_comparison_csv(build_variants, &comparison.targets, |k| k.clone(), by_ratio)),
+                BrowsingComparisonSelectedTable::Includes => ("includes_variants.csv", csv_export::variant_comparison_csv(build_variants, &comparison.includes, |k| k.clone(), by_ratio)),
+                BrowsingComparisonSelectedTable::Sources => ("sources_variants.csv", csv_export::variant_comparison_csv(build_variants, &comparison.sources, |k| k.clone(), by_ratio)),
+                BrowsingComparisonSelectedTable::Frontend => ("frontend_variants.csv", csv_export::variant_comparison_csv(build_variants, &comparison.frontend_operations, |k| format!("{} {}", k.1.short_name(), k.0), by_ratio)),
+                BrowsingComparisonSelectedTable::Backend => ("backend_variants.csv", csv_export::variant_comparison_csv(build_variants, &comparison.backend_operations, |k| k.clone(), by_ratio)),
+            };
+
+            Some(csv)
+        }
+    }
+}
+
+// Compare the loaded configurations, `analysis` stands for its own configuration
+fn compare_loaded_variants(analysis: &AnalyisisResult, loaded: &LoadedVariants) -> VariantComparison {
+    let summaries: Vec<_> = loaded.build_variants
+        .iter()
+        .map(|build_variant| {
+            let summary = loaded.others
+                .iter()
+                .find(|(v, _)| v == build_variant)
+                .map(|(_, other)| &other.summary)
+                .unwrap_or(&analysis.summary);
+
+            (build_variant.clone(), summary)
+        })
+        .collect();
+
+    crate::processing::variant_comparison::compare_variants(&summaries)
+}
+
 impl App {
 
     fn default_state(persistance: &Persistance) -> AppState {
@@ -142,7 +626,7 @@ impl App {
 
         AppState::LookingForFiles {
             current_path: String::new(),
-            build_variants: combo_box::State::new(persistance::BuildVariant::ALL.into()),
+            build_variants: combo_box::State::new(Vec::new()),
             build_variant: last_selected_build_variant,
         }
     }
@@ -162,25 +646,71 @@ impl App {
         )
     }
 
-    fn open_path(&mut self, path: &str, build_variant: persistance::BuildVariant) {
+    // Keep the selected build variant when the path has trace files for it, otherwise switch to the
+    // configuration with the most trace files
+    fn select_build_variant(
+        discovered: Vec<persistance::DiscoveredBuildVariant>,
+        build_variant: &persistance::BuildVariant,
+    ) -> (combo_box::State<persistance::DiscoveredBuildVariant>, persistance::BuildVariant) {
+        let is_available = discovered.iter().any(|d| d.build_variant == *build_variant);
 
-        let analysis = crate::processing::analyze_path(path, build_variant);
+        let build_variant = match discovered.iter().max_by_key(|d| (d.build_variant != persistance::BuildVariant::SingleConfig, d.trace_count)) {
+            Some(best) if !is_available => best.build_variant.clone(),
+            _ => build_variant.clone(),
+        };
 
-        match analysis {
-            Ok(analysis) => {
-                event!(Level::INFO, "Analysis complete");
-                self.state = AppState::AnalyzingFiles(
-                    AnalyzingFilesState {
-                        analysis,
-                        browsing_pane: BrowsingPane::Summary {
-                            selected_option: BrowsingSummarySelectedOption::Alpha,
-                        }
-                    }
-                );
+        (combo_box::State::new(discovered), build_variant)
+    }
+
+    // Start the analysis in the background, the browsing state is entered once it finishes
+    fn open_path(
+        &mut self,
+        path: &str,
+        build_variant: persistance::BuildVariant,
+        file_cache: FileCache,
+        baseline: Option<ComparisonState>,
+    ) -> Task<Message> {
+        let (state, task) = loading::start(path.to_string(), build_variant, file_cache, baseline);
+        self.state = AppState::Loading(Box::new(state));
+        task
+    }
+
+    fn select_translation_unit_table(&mut self, table: BrowsingTranslationUnitSelectedTable) {
+        if let AppState::AnalyzingFiles(state) = &mut self.state {
+            if let BrowsingPane::TranslationUnit { source: _, selected_table } = &mut state.browsing_pane {
+                *selected_table = table;
+            }
+        }
+    }
+
+    fn select_backend_option(&mut self, option: BrowsingBackendSelectedOption) {
+        if let AppState::AnalyzingFiles(state) = &mut self.state {
+            if let BrowsingPane::Backend { selected_option, full_name_display: _ } = &mut state.browsing_pane {
+                *selected_option = option;
+            }
+        }
+    }
+
+    fn select_comparison_table(&mut self, table: BrowsingComparisonSelectedTable) {
+        if let AppState::AnalyzingFiles(state) = &mut self.state {
+            if let BrowsingPane::Comparison { selected_table, selected_option: _ } = &mut state.browsing_pane {
+                *selected_table = table;
             }
-            Err(e) => {
-                event!(Level::ERROR, "Analysis error: {:?}", e);
-                self.cross_state_cache.errors.push(format!("Analysis error: {:?}", e));
+        }
+    }
+
+    fn select_variants_table(&mut self, table: BrowsingComparisonSelectedTable) {
+        if let AppState::AnalyzingFiles(state) = &mut self.state {
+            if let BrowsingPane::Variants { selected_table, selected_option: _ } = &mut state.browsing_pane {
+                *selected_table = table;
+            }
+        }
+    }
+
+    fn select_variants_option(&mut self, option: BrowsingVariantsSelectedOption) {
+        if let AppState::AnalyzingFiles(state) = &mut self.state {
+            if let BrowsingPane::Variants { selected_table: _, selected_option } = &mut state.browsing_pane {
+                *selected_option = option;
             }
         }
     }
@@ -189,21 +719,47 @@ impl App {
         match message {
             Message::BrowseInputChanged(path) => {
                 if let AppState::LookingForFiles { current_path, build_variants: _, build_variant: _ } = &mut self.state {
-                    *current_path = path;
+                    *current_path = path.clone();
+                    return discovery::settle(path);
+                }
+            }
+            Message::BrowseInputSettled(path) => {
+                // Only search the build directory once the typing stopped
+                if let AppState::LookingForFiles { current_path, build_variants: _, build_variant: _ } = &self.state {
+                    if *current_path == path {
+                        return discovery::discover(path, Message::BuildVariantsDiscovered);
+                    }
+                }
+            }
+            Message::BuildVariantsDiscovered(path, discovered) => {
+                if let AppState::LookingForFiles { current_path, build_variants, build_variant } = &mut self.state {
+                    if *current_path == path {
+                        (*build_variants, *build_variant) = Self::select_build_variant(discovered, build_variant);
+                    }
                 }
             }
             Message::BrowseLastItemOpen(path) => {
-                if let AppState::LookingForFiles { current_path: _, build_variants: _, build_variant } = &self.state {
-                    self.open_path(&path, *build_variant);
+                if let AppState::LookingForFiles { current_path, build_variants: _, build_variant: _ } = &mut self.state {
+                    // The path is opened once its build variants are discovered
+                    current_path.clone_from(&path);
+                    return discovery::discover(path, Message::LastItemVariantsDiscovered);
+                }
+            }
+            Message::LastItemVariantsDiscovered(path, discovered) => {
+                if let AppState::LookingForFiles { current_path, build_variants: _, build_variant } = &self.state {
+                    if *current_path == path {
+                        let (_, build_variant) = Self::select_build_variant(discovered, build_variant);
+                        return self.open_path(&path, build_variant, FileCache::default(), None);
+                    }
                 }
             }
             Message::BrowserRemoveLastItem(path) => {
                 self.cross_state_cache.persistance.remove_last_path(&path);
             }
-            Message::BrowseSelectedBuildVariant(build_variant) => {
+            Message::BrowseSelectedBuildVariant(discovered) => {
                 if let AppState::LookingForFiles { current_path: _, build_variants: _, build_variant: current_build_variant } = &mut self.state {
-                    *current_build_variant = build_variant;
-                    self.cross_state_cache.persistance.push_build_variant(build_variant);
+                    *current_build_variant = discovered.build_variant.clone();
+                    self.cross_state_cache.persistance.push_build_variant(discovered.build_variant);
                 }
             }
             Message::BrowseClicked => {
@@ -216,20 +772,198 @@ impl App {
                         return Task::none();
                     }
 
-                    self.cross_state_cache.persistance.push_last_path(current_path, persistance::BuildVariant::Release);
+                    self.cross_state_cache.persistance.push_last_path(current_path, build_variant.clone());
+
+                    return self.open_path(&current_path.clone(), build_variant.clone(), FileCache::default(), None);
+                }
+            }
+
+            Message::AnalysisProgressed(progress) => {
+                if let AppState::Loading(state) = &mut self.state {
+                    state.progress = progress;
+                }
+            }
+            Message::AnalysisFinished(outcome) => {
+                let AppState::Loading(state) = &mut self.state else {
+                    return Task::none();
+                };
+
+                match outcome.take() {
+                    Some(Ok(analysis)) => {
+                        event!(Level::INFO, "Analysis complete in {:.1} s", state.started.elapsed().as_secs_f64());
+
+                        // Keep comparing against the same baseline after a refresh
+                        let comparison = state.baseline.take().map(|c| ComparisonState::new(c.baseline, &analysis));
 
-                    self.open_path(&current_path.clone(), *build_variant);
+                        self.state = AppState::AnalyzingFiles(
+                            Box::new(AnalyzingFilesState {
+                                analysis,
+                                browsing_pane: BrowsingPane::Summary {
+                                    selected_option: BrowsingSummarySelectedOption::Alpha,
+                                },
+                                notice: None,
+                                baseline_input: String::new(),
+                                comparison,
+                                baseline_loading: None,
+                                variant_comparison: None,
+                                variants_loading: None,
+                                watching: false,
+                                watch_in_flight: None,
+                                filters: HashMap::new(),
+                            })
+                        );
+                    }
+                    Some(Err(e)) => {
+                        event!(Level::ERROR, "Analysis error: {}", e);
+                        self.cross_state_cache.errors.push(format!("Analysis error: {}", e));
+                        self.state = Self::default_state(&self.cross_state_cache.persistance);
+                    }
+                    None => {}
+                }
+            }
+            Message::AnalysisCancelClicked => {
+                if let AppState::Loading(state) = &self.state {
+                    event!(Level::INFO, "Analysis cancelled");
+                    state.cancel();
+                    self.state = Self::default_state(&self.cross_state_cache.persistance);
                 }
             }
 
             Message::BrowseCloseClicked => {
+                if let AppState::AnalyzingFiles(state) = &self.state {
+                    for loading in state.baseline_loading.iter().chain(state.variants_loading.iter()) {
+                        loading.cancel();
+                    }
+                }
                 self.state = Self::default_state(&self.cross_state_cache.persistance);
             }
             Message::BrowseRefreshClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    let path = state.analysis.selected_path.clone();
-                    let build_variant = state.analysis.build_variant;
-                    self.open_path(&path, build_variant);
+                    let path = state.analysis.reload_path().to_string();
+                    let build_variant = state.analysis.build_variant.clone();
+                    let baseline = state.comparison.take();
+
+                    for loading in state.baseline_loading.take().into_iter().chain(state.variants_loading.take()) {
+                        loading.cancel();
+                    }
+
+                    // Only the trace files which changed since the last analysis are parsed again
+                    let file_cache = state.analysis.take_file_cache();
+
+                    return self.open_path(&path, build_variant, file_cache, baseline);
+                }
+            }
+
+            Message::BrowseWatchClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if state.analysis.report_path.is_some() {
+                        state.notice = Some("Watching is only possible for build directories".to_string());
+                    } else {
+                        state.watching = !state.watching;
+                        state.notice = state.watching.then(|| format!("Watching {}", state.analysis.resolved_cmake_files_path));
+                    }
+                }
+            }
+            Message::WatchTick => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if state.watching && state.watch_in_flight.is_none() {
+                        let (id, task) = watch::reanalyze(
+                            state.analysis.selected_path.clone(),
+                            state.analysis.build_variant.clone(),
+                            state.analysis.take_file_cache(),
+                        );
+
+                        state.watch_in_flight = Some(id);
+                        return task;
+                    }
+                }
+            }
+            Message::WatchAnalysisFinished(id, outcome) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    // Started for an analysis which was closed or refreshed since
+                    if state.watch_in_flight != Some(id) {
+                        return Task::none();
+                    }
+
+                    state.watch_in_flight = None;
+
+                    match outcome.take() {
+                        Some(Ok(analysis)) => {
+                            // Only the analysis is replaced, the selected pane stays as it is
+                            state.analysis = analysis;
+
+                            if let Some(comparison) = state.comparison.take() {
+                                state.comparison = Some(ComparisonState::new(comparison.baseline, &state.analysis));
+                            }
+
+                            // The displayed item could be gone if its trace file was removed
+                            let summary = &state.analysis.summary;
+                            match &mut state.browsing_pane {
+                                BrowsingPane::Includes { selected_option: _, includers_display }
+                                    if includers_display.as_ref().is_some_and(|k| !summary.frontend_file_process_summaries.contains_key(k)) => {
+                                    *includers_display = None;
+                                }
+                                BrowsingPane::Frontend { selected_option: _, selected_grouping: BrowsingFrontendSelectedGrouping::Operations, full_name_display }
+                                    if full_name_display.as_ref().is_some_and(|k| !summary.frontend_operation_summaries.contains_key(k)) => {
+                                    *full_name_display = None;
+                                }
+                                BrowsingPane::Frontend { selected_option: _, selected_grouping: BrowsingFrontendSelectedGrouping::Templates, full_name_display }
+                                    if full_name_display.as_ref().is_some_and(|k| !summary.template_groups.contains_key(k)) => {
+                                    *full_name_display = None;
+                                }
+                                BrowsingPane::Backend { selected_option: _, full_name_display }
+                                    if full_name_display.as_ref().is_some_and(|k| !summary.backend_operation_summaries.contains_key(k)) => {
+                                    *full_name_display = None;
+                                }
+                                _ => {}
+                            }
+                        }
+                        Some(Err(e)) => {
+                            event!(Level::ERROR, "Watch error: {}", e);
+                            state.notice = Some(format!("Watch error: {}", e));
+                            state.watching = false;
+                        }
+                        None => {}
+                    }
+                }
+            }
+
+            Message::BrowseExportJsonClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    let path = state.analysis.export_dir().join(crate::processing::report::REPORT_FILE_NAME);
+                    let path = path.to_string_lossy();
+
+                    match crate::processing::report::write_report(&state.analysis, &path) {
+                        Ok(()) => {
+                            event!(Level::INFO, "Report written to {}", path);
+                            state.notice = Some(format!("Report written to {}", path));
+                        }
+                        Err(e) => {
+                            event!(Level::ERROR, "Export error: {}", e);
+                            state.notice = Some(format!("Export error: {}", e));
+                        }
+                    }
+                }
+            }
+
+            Message::BrowseExportCsvClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    let Some((file_name, content)) = current_pane_csv(state) else {
+                        state.notice = Some("Nothing to export in this pane".to_string());
+                        return Task::none();
+                    };
+                    let path = state.analysis.export_dir().join(file_name);
+
+                    match crate::processing::csv_export::write_csv(&path, &content) {
+                        Ok(()) => {
+                            event!(Level::INFO, "Table written to {}", path.display());
+                            state.notice = Some(format!("Table written to {}", path.display()));
+                        }
+                        Err(e) => {
+                            event!(Level::ERROR, "Export error: {}", e);
+                            state.notice = Some(format!("Export error: {}", e));
+                        }
+                    }
                 }
             }
 
@@ -246,6 +980,7 @@ impl App {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
                     state.browsing_pane = BrowsingPane::Includes {
                         selected_option: BrowsingIncludesSelectedOption::TotalTime,
+                        includers_display: None,
                     }
                 }
             }
@@ -256,10 +991,19 @@ impl App {
                     }
                 }
             }
+            Message::BrowseTopLevelPaneIncludeTreeClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::IncludeTree {
+                        selected_option: BrowsingIncludeTreeSelectedOption::TranslationUnits,
+                        expanded: BTreeSet::new(),
+                    }
+                }
+            }
             Message::BrowseTopLevelPaneFrontendClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
                     state.browsing_pane = BrowsingPane::Frontend {
                         selected_option: BrowsingFrontendSelectedOption::TotalTime,
+                        selected_grouping: BrowsingFrontendSelectedGrouping::Operations,
                         full_name_display: None,
                     }
                 }
@@ -267,6 +1011,7 @@ impl App {
             Message::BrowseTopLevelPaneBackendClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
                     state.browsing_pane = BrowsingPane::Backend {
+                        selected_option: BrowsingBackendSelectedOption::Symbols,
                         full_name_display: None,
                     }
                 }
@@ -305,18 +1050,32 @@ impl App {
             // Include pane
             Message::BrowseIncludePaneTotalTimeClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    if let BrowsingPane::Includes { selected_option } = &mut state.browsing_pane {
+                    if let BrowsingPane::Includes { selected_option, includers_display: _ } = &mut state.browsing_pane {
                         *selected_option = BrowsingIncludesSelectedOption::TotalTime;
                     }
                 }
             }
             Message::BrowseIncludePaneSelfTimeClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    if let BrowsingPane::Includes { selected_option } = &mut state.browsing_pane {
+                    if let BrowsingPane::Includes { selected_option, includers_display: _ } = &mut state.browsing_pane {
                         *selected_option = BrowsingIncludesSelectedOption::SelfTime;
                     }
                 }
             }
+            Message::BrowseIncludeIncludersClicked(include) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Includes { selected_option: _, includers_display } = &mut state.browsing_pane {
+                        *includers_display = Some(include);
+                    }
+                }
+            }
+            Message::BrowseIncludeIncludersClosed => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Includes { selected_option: _, includers_display } = &mut state.browsing_pane {
+                        *includers_display = None;
+                    }
+                }
+            }
 
             // Source pane
             Message::BrowseSourcePaneTotalTimeClicked => {
@@ -340,32 +1099,138 @@ impl App {
                     }
                 }
             }
+            Message::BrowseSourcePaneFlagGroupsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Sources { selected_option } = &mut state.browsing_pane {
+                        *selected_option = BrowsingSourcesSelectedOption::FlagGroups;
+                    }
+                }
+            }
+            Message::BrowseSourceTranslationUnitClicked(source) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::TranslationUnit {
+                        source,
+                        selected_table: BrowsingTranslationUnitSelectedTable::Includes,
+                    }
+                }
+            }
+
+            // Translation unit pane
+            Message::BrowseTranslationUnitClosed => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::Sources {
+                        selected_option: BrowsingSourcesSelectedOption::TotalTime,
+                    }
+                }
+            }
+            Message::BrowseTranslationUnitIncludesClicked => self.select_translation_unit_table(BrowsingTranslationUnitSelectedTable::Includes),
+            Message::BrowseTranslationUnitFrontendClicked => self.select_translation_unit_table(BrowsingTranslationUnitSelectedTable::Frontend),
+            Message::BrowseTranslationUnitBackendClicked => self.select_translation_unit_table(BrowsingTranslationUnitSelectedTable::Backend),
+            Message::BrowseTranslationUnitPassesClicked => self.select_translation_unit_table(BrowsingTranslationUnitSelectedTable::Passes),
+            Message::BrowseTranslationUnitFlameChartClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::TranslationUnit { source, selected_table: _ } = &state.browsing_pane {
+                        let chart = match state.analysis.summary.translation_units.get(source) {
+                            Some(file) => crate::processing::flame_chart::load_flame_chart(&file.trace_path).map_err(|e| e.to_string()),
+                            None => Err(format!("No trace file for {}", source)),
+                        };
+
+                        state.browsing_pane = BrowsingPane::FlameChart {
+                            source: source.clone(),
+                            chart,
+                        }
+                    }
+                }
+            }
+
+            // Flame chart pane
+            Message::BrowseFlameChartClosed => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::FlameChart { source, chart: _ } = &mut state.browsing_pane {
+                        state.browsing_pane = BrowsingPane::TranslationUnit {
+                            source: std::mem::take(source),
+                            selected_table: BrowsingTranslationUnitSelectedTable::Includes,
+                        }
+                    }
+                }
+            }
+
+            // Include tree pane
+            Message::BrowseIncludeTreeTranslationUnitsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::IncludeTree { selected_option, expanded } = &mut state.browsing_pane {
+                        *selected_option = BrowsingIncludeTreeSelectedOption::TranslationUnits;
+                        expanded.clear();
+                    }
+                }
+            }
+            Message::BrowseIncludeTreeAggregatedClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::IncludeTree { selected_option, expanded } = &mut state.browsing_pane {
+                        *selected_option = BrowsingIncludeTreeSelectedOption::Aggregated;
+                        expanded.clear();
+                    }
+                }
+            }
+            Message::BrowseIncludeTreeToggled(path) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::IncludeTree { selected_option: _, expanded } = &mut state.browsing_pane {
+                        if !expanded.remove(&path) {
+                            expanded.insert(path);
+                        }
+                    }
+                }
+            }
 
             // Frontend pane
             Message::BrowseFrontendTotalTimeClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    if let BrowsingPane::Frontend { selected_option, full_name_display: _ } = &mut state.browsing_pane {
+                    if let BrowsingPane::Frontend { selected_option, selected_grouping: _, full_name_display: _ } = &mut state.browsing_pane {
                         *selected_option = BrowsingFrontendSelectedOption::TotalTime;
                     }
                 }
             }
             Message::BrowseFrontendSelfTimeClicked => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    if let BrowsingPane::Frontend { selected_option, full_name_display: _ } = &mut state.browsing_pane {
+                    if let BrowsingPane::Frontend { selected_option, selected_grouping: _, full_name_display: _ } = &mut state.browsing_pane {
                         *selected_option = BrowsingFrontendSelectedOption::SelfTime;
                     }
                 }
             }
+            Message::BrowseFrontendOperationsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Frontend { selected_option: _, selected_grouping, full_name_display } = &mut state.browsing_pane {
+                        *selected_grouping = BrowsingFrontendSelectedGrouping::Operations;
+                        *full_name_display = None;
+                    }
+                }
+            }
+            Message::BrowseFrontendTemplatesClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Frontend { selected_option: _, selected_grouping, full_name_display } = &mut state.browsing_pane {
+                        *selected_grouping = BrowsingFrontendSelectedGrouping::Templates;
+                        *full_name_display = None;
+                    }
+                }
+            }
+            Message::BrowseFrontendInstantiationClicked(full_name) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Frontend { selected_option: _, selected_grouping, full_name_display } = &mut state.browsing_pane {
+                        *selected_grouping = BrowsingFrontendSelectedGrouping::Operations;
+                        *full_name_display = Some(full_name);
+                    }
+                }
+            }
             Message::BrowseFrontendFullNameClicked(full_name) => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    if let BrowsingPane::Frontend { selected_option: _, full_name_display } = &mut state.browsing_pane {
+                    if let BrowsingPane::Frontend { selected_option: _, selected_grouping: _, full_name_display } = &mut state.browsing_pane {
                         *full_name_display = Some(full_name);
                     }
                 }
             }
             Message::BrowseFrontendFullNameClosed => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
-                    if let BrowsingPane::Frontend { selected_option: _, full_name_display } = &mut state.browsing_pane {
+                    if let BrowsingPane::Frontend { selected_option: _, selected_grouping: _, full_name_display } = &mut state.browsing_pane {
                         *full_name_display = None;
                     }
                 }
@@ -375,6 +1240,7 @@ impl App {
             Message::BrowseBackendFullNameClicked(full_name) => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
                     state.browsing_pane = BrowsingPane::Backend {
+                        selected_option: BrowsingBackendSelectedOption::Symbols,
                         full_name_display: Some(full_name),
                     }
                 }
@@ -382,10 +1248,277 @@ impl App {
             Message::BrowseBackendFullNameClosed => {
                 if let AppState::AnalyzingFiles(state) = &mut self.state {
                     state.browsing_pane = BrowsingPane::Backend {
+                        selected_option: BrowsingBackendSelectedOption::Symbols,
                         full_name_display: None,
                     }
                 }
             }
+            Message::BrowseBackendSymbolsClicked => self.select_backend_option(BrowsingBackendSelectedOption::Symbols),
+            Message::BrowseBackendPassTotalTimeClicked => self.select_backend_option(BrowsingBackendSelectedOption::PassTotalTime),
+            Message::BrowseBackendPassSelfTimeClicked => self.select_backend_option(BrowsingBackendSelectedOption::PassSelfTime),
+
+            // Timeline pane
+            Message::BrowseTopLevelPaneTimelineClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::Timeline;
+                }
+            }
+
+            // Critical path pane
+            Message::BrowseTopLevelPaneCriticalPathClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::CriticalPath {
+                        selected_option: BrowsingCriticalPathSelectedOption::Chain,
+                    };
+                }
+            }
+            Message::BrowseCriticalPathPaneChainClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::CriticalPath { selected_option } = &mut state.browsing_pane {
+                        *selected_option = BrowsingCriticalPathSelectedOption::Chain;
+                    }
+                }
+            }
+            Message::BrowseCriticalPathPaneContributionClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::CriticalPath { selected_option } = &mut state.browsing_pane {
+                        *selected_option = BrowsingCriticalPathSelectedOption::Contribution;
+                    }
+                }
+            }
+
+            // Comparison pane
+            Message::BrowseTopLevelPaneComparisonClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::Comparison {
+                        selected_table: BrowsingComparisonSelectedTable::Includes,
+                        selected_option: BrowsingComparisonSelectedOption::Regressions,
+                    }
+                }
+            }
+            Message::BrowseFilterChanged(text) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    let filter = state.filters.entry(std::mem::discriminant(&state.browsing_pane)).or_default();
+                    filter.text = text;
+                    filter.update();
+                }
+            }
+            Message::BrowseFilterRegexClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    let filter = state.filters.entry(std::mem::discriminant(&state.browsing_pane)).or_default();
+                    filter.regex = !filter.regex;
+                    filter.update();
+                }
+            }
+            Message::BrowseComparisonBaselineInputChanged(path) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.baseline_input = path;
+                }
+            }
+            Message::BrowseComparisonLoadClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if state.baseline_loading.is_none() {
+                        let (loading, task) = loading::start_baseline(state.baseline_input.clone(), state.analysis.build_variant.clone());
+                        state.baseline_loading = Some(loading);
+                        return task;
+                    }
+                }
+            }
+            Message::BrowseComparisonCancelClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let Some(loading) = state.baseline_loading.take() {
+                        event!(Level::INFO, "Baseline loading cancelled");
+                        loading.cancel();
+                    }
+                }
+            }
+            Message::BaselineProgressed(progress) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let Some(loading) = &mut state.baseline_loading {
+                        loading.progress = progress;
+                    }
+                }
+            }
+            Message::BaselineLoaded(outcome) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if state.baseline_loading.take().is_some() {
+                        match outcome.take() {
+                            Some(Ok(baseline)) => {
+                                event!(Level::INFO, "Baseline loaded");
+                                state.comparison = Some(ComparisonState::new(baseline, &state.analysis));
+                                state.notice = None;
+                            }
+                            Some(Err(e)) => {
+                                event!(Level::ERROR, "Baseline error: {}", e);
+                                state.notice = Some(format!("Baseline error: {}", e));
+                            }
+                            None => {}
+                        }
+                    }
+                }
+            }
+            Message::BrowseComparisonTargetsClicked => self.select_comparison_table(BrowsingComparisonSelectedTable::Targets),
+            Message::BrowseComparisonIncludesClicked => self.select_comparison_table(BrowsingComparisonSelectedTable::Includes),
+            Message::BrowseComparisonSourcesClicked => self.select_comparison_table(BrowsingComparisonSelectedTable::Sources),
+            Message::BrowseComparisonFrontendClicked => self.select_comparison_table(BrowsingComparisonSelectedTable::Frontend),
+            Message::BrowseComparisonBackendClicked => self.select_comparison_table(BrowsingComparisonSelectedTable::Backend),
+            Message::BrowseComparisonRegressionsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Comparison { selected_table: _, selected_option } = &mut state.browsing_pane {
+                        *selected_option = BrowsingComparisonSelectedOption::Regressions;
+                    }
+                }
+            }
+            Message::BrowseComparisonImprovementsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Comparison { selected_table: _, selected_option } = &mut state.browsing_pane {
+                        *selected_option = BrowsingComparisonSelectedOption::Improvements;
+                    }
+                }
+            }
+
+            // Namespaces pane
+            Message::BrowseTopLevelPaneNamespacesClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::Namespaces {
+                        selected_option: BrowsingNamespacesSelectedOption::Total,
+                        expanded: BTreeSet::new(),
+                    }
+                }
+            }
+            Message::BrowseNamespacesTotalClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Namespaces { selected_option, expanded: _ } = &mut state.browsing_pane {
+                        *selected_option = BrowsingNamespacesSelectedOption::Total;
+                    }
+                }
+            }
+            Message::BrowseNamespacesFrontendClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Namespaces { selected_option, expanded: _ } = &mut state.browsing_pane {
+                        *selected_option = BrowsingNamespacesSelectedOption::Frontend;
+                    }
+                }
+            }
+            Message::BrowseNamespacesBackendClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Namespaces { selected_option, expanded: _ } = &mut state.browsing_pane {
+                        *selected_option = BrowsingNamespacesSelectedOption::Backend;
+                    }
+                }
+            }
+            Message::BrowseNamespacesToggled(path) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Namespaces { selected_option: _, expanded } = &mut state.browsing_pane {
+                        if !expanded.remove(&path) {
+                            expanded.insert(path);
+                        }
+                    }
+                }
+            }
+
+            // Events pane
+            Message::BrowseTopLevelPaneEventsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::Events {
+                        selected_option: BrowsingEventsSelectedOption::TotalTime,
+                        selected_name: None,
+                    }
+                }
+            }
+            Message::BrowseEventsTotalTimeClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Events { selected_option, selected_name: _ } = &mut state.browsing_pane {
+                        *selected_option = BrowsingEventsSelectedOption::TotalTime;
+                    }
+                }
+            }
+            Message::BrowseEventsSelfTimeClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Events { selected_option, selected_name: _ } = &mut state.browsing_pane {
+                        *selected_option = BrowsingEventsSelectedOption::SelfTime;
+                    }
+                }
+            }
+            Message::BrowseEventsNameClicked(name) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Events { selected_option: _, selected_name } = &mut state.browsing_pane {
+                        *selected_name = Some(name);
+                    }
+                }
+            }
+            Message::BrowseEventsNameClosed => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let BrowsingPane::Events { selected_option: _, selected_name } = &mut state.browsing_pane {
+                        *selected_name = None;
+                    }
+                }
+            }
+
+            // Configurations pane
+            Message::BrowseTopLevelPaneVariantsClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    state.browsing_pane = BrowsingPane::Variants {
+                        selected_table: BrowsingComparisonSelectedTable::Includes,
+                        selected_option: BrowsingVariantsSelectedOption::TotalTime,
+                    }
+                }
+            }
+            Message::BrowseVariantsAnalyzeClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if state.analysis.report_path.is_some() {
+                        state.notice = Some("Configurations error: a report holds a single configuration, open the build directory instead".to_string());
+                    } else if state.variants_loading.is_none() {
+                        let (loading, task) = loading::start_variants(
+                            state.analysis.selected_path.clone(),
+                            state.analysis.build_variant.clone(),
+                        );
+                        state.variants_loading = Some(loading);
+                        return task;
+                    }
+                }
+            }
+            Message::BrowseVariantsCancelClicked => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let Some(loading) = state.variants_loading.take() {
+                        event!(Level::INFO, "Configurations analysis cancelled");
+                        loading.cancel();
+                    }
+                }
+            }
+            Message::VariantsProgressed(build_variant, progress) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if let Some(loading) = &mut state.variants_loading {
+                        loading.build_variant = Some(build_variant);
+                        loading.progress = progress;
+                    }
+                }
+            }
+            Message::VariantsAnalyzed(outcome) => {
+                if let AppState::AnalyzingFiles(state) = &mut self.state {
+                    if state.variants_loading.take().is_some() {
+                        match outcome.take() {
+                            Some(Ok(loaded)) => {
+                                event!(Level::INFO, "Configurations analyzed");
+                                state.variant_comparison = Some(compare_loaded_variants(&state.analysis, &loaded));
+                                state.notice = None;
+                            }
+                            Some(Err(e)) => {
+                                event!(Level::ERROR, "Configurations error: {}", e);
+                                state.notice = Some(format!("Configurations error: {}", e));
+                            }
+                            None => {}
+                        }
+                    }
+                }
+            }
+            Message::BrowseVariantsTargetsClicked => self.select_variants_table(BrowsingComparisonSelectedTable::Targets),
+            Message::BrowseVariantsIncludesClicked => self.select_variants_table(BrowsingComparisonSelectedTable::Includes),
+            Message::BrowseVariantsSourcesClicked => self.select_variants_table(BrowsingComparisonSelectedTable::Sources),
+            Message::BrowseVariantsFrontendClicked => self.select_variants_table(BrowsingComparisonSelectedTable::Frontend),
+            Message::BrowseVariantsBackendClicked => self.select_variants_table(BrowsingComparisonSelectedTable::Backend),
+            Message::BrowseVariantsTotalTimeClicked => self.select_variants_option(BrowsingVariantsSelectedOption::TotalTime),
+            Message::BrowseVariantsRatioClicked => self.select_variants_option(BrowsingVariantsSelectedOption::Ratio),
 
             // Ignore this message always
             Message::Dummy(_) => {}
@@ -403,23 +1536,33 @@ impl App {
         "Clang Build Time Processor".into()
     }
 
+    pub fn subscription(&self) -> Subscription<Message> {
+        match &self.state {
+            AppState::AnalyzingFiles(state) if state.watching => Subscription::run(watch::ticks),
+            _ => Subscription::none(),
+        }
+    }
+
     pub fn view(&self) -> Element<Message> {
         match &self.state {
             AppState::LookingForFiles{ current_path, build_variants, build_variant } => {
 
                 let recent_files = self.cross_state_cache.persistance.last_paths();
 
-                let custom_input = text_input("Enter source path", current_path)
+                let custom_input = text_input("Enter source path or report file", current_path)
                     .font(style::MONO)
                     .on_input(Message::BrowseInputChanged);
 
+                // The persisted variant is shown as the placeholder until a path with trace files is entered
+                let selected_build_variant = build_variants.options().iter().find(|d| d.build_variant == *build_variant);
+
                 let build_variant_selector = combo_box(
                     build_variants,
-                    "Select the build variant",
-                    Some(build_variant),
+                    &build_variant.to_string(),
+                    selected_build_variant,
                     Message::BrowseSelectedBuildVariant,
                 )
-                    .width(140);
+                    .width(200);
 
                 let first_row = Row::new()
                     .push(custom_input)
@@ -432,6 +1575,10 @@ impl App {
 
                 let mut last_files_component = Column::new();
 
+                if let Some(error) = self.cross_state_cache.errors.last() {
+                    last_files_component = last_files_component.push(Text::new(error).font(style::MONO));
+                }
+
                 for file in recent_files {
                     let row = Row::new()
                         .push(button("DEL").style(iced::widget::button::danger).on_press_with(|| Message::BrowserRemoveLastItem(file.into())))
@@ -448,6 +1595,9 @@ impl App {
                     .padding(10)
                     .into()
             }
+            AppState::Loading(state) => {
+                loading::view(state)
+            }
             AppState::AnalyzingFiles(state) => {
                 browsing::top_level_selector::view(state)
             }
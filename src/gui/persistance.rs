@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 pub type BuildVariant = crate::model::BuildVariant;
+pub type DiscoveredBuildVariant = crate::model::DiscoveredBuildVariant;
 
 pub struct Persistance {
     persistance_file_path: String,
@@ -30,7 +31,7 @@ impl Persistance {
                 return Persistance {
                     persistance_file_path,
                     last_files: Vec::new(),
-                    last_selected_build_variant: BuildVariant::Release,
+                    last_selected_build_variant: BuildVariant::default(),
                     is_functional: false,
                 };
             }
@@ -49,7 +50,7 @@ impl Persistance {
                 Persistance {
                     persistance_file_path,
                     last_files: Vec::new(),
-                    last_selected_build_variant: BuildVariant::Release,
+                    last_selected_build_variant: BuildVariant::default(),
                     is_functional: false,
                 }
             }
@@ -57,7 +58,7 @@ impl Persistance {
             Persistance {
                 persistance_file_path: String::new(),
                 last_files: Vec::new(),
-                last_selected_build_variant: BuildVariant::Release,
+                last_selected_build_variant: BuildVariant::default(),
                 is_functional: false,
             }
         }
@@ -81,10 +82,12 @@ impl Persistance {
     }
 
     pub fn build_variant(&self) -> BuildVariant {
-        self.last_selected_build_variant
+        self.last_selected_build_variant.clone()
     }
 
     pub fn push_build_variant(&mut self, build_variant: BuildVariant) {
+        self.last_selected_build_variant = build_variant.clone();
+
         let persistance = PersistanceFormat {
             last_paths: self.last_files.clone(),
             last_selected_build_variant: build_variant,
@@ -104,7 +107,7 @@ impl Persistance {
 
         let persistance = PersistanceFormat {
             last_paths: self.last_files.clone(),
-            last_selected_build_variant: self.last_selected_build_variant,
+            last_selected_build_variant: self.last_selected_build_variant.clone(),
         };
 
         let persistance_json = serde_json::to_string(&persistance).unwrap();
@@ -0,0 +1,32 @@
+use iced::{widget::canvas, Theme};
+
+use super::style::MONO;
+
+// Approximate width of a character and height of a line of the tooltip text
+const CHAR_WIDTH: f32 = 7.0;
+const LINE_HEIGHT: f32 = 14.0;
+
+// Draw `lines` in a box next to the cursor `position`, moved to the other side when it would leave `bounds`
+pub fn draw(frame: &mut canvas::Frame, theme: &Theme, bounds: iced::Rectangle, position: iced::Point, lines: &[String]) {
+    let palette = theme.extended_palette();
+
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or_default() as f32 * CHAR_WIDTH + 8.0;
+    let height = lines.len() as f32 * LINE_HEIGHT + 8.0;
+
+    let x = if position.x + 12.0 + width > bounds.width { (position.x - width - 4.0).max(0.0) } else { position.x + 12.0 };
+    let y = if position.y + 12.0 + height > bounds.height { (position.y - height - 4.0).max(0.0) } else { position.y + 12.0 };
+
+    let background = canvas::Path::rectangle(iced::Point::new(x, y), iced::Size::new(width, height));
+    frame.fill(&background, palette.background.strong.color);
+
+    for (i, line) in lines.iter().enumerate() {
+        frame.fill_text(canvas::Text {
+            content: line.clone(),
+            position: iced::Point::new(x + 4.0, y + 4.0 + i as f32 * LINE_HEIGHT),
+            color: palette.background.strong.text,
+            size: 11.0.into(),
+            font: MONO,
+            ..Default::default()
+        });
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use iced::futures::channel::{mpsc, oneshot};
+use iced::futures::Stream;
+use iced::Task;
+
+use crate::processing::{AnalysisError, FileCache};
+
+use super::{loading::AnalysisOutcome, persistance, Message};
+
+// How often the build directory is checked for new or modified trace files
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+// Every re-analysis gets its own id, a result arriving after the browsed analysis changed is dropped by it
+static NEXT_REANALYSIS_ID: AtomicU64 = AtomicU64::new(0);
+
+// Emits `WatchTick` every `WATCH_INTERVAL`, the thread stops once the subscription is dropped
+pub fn ticks() -> impl Stream<Item = Message> {
+    let (sender, receiver) = mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+
+            if sender.unbounded_send(Message::WatchTick).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+// Analyze `path` again on a background thread. Only the trace files which are not in `file_cache`
+// or changed since are parsed, so this is cheap while the build adds files one by one.
+// Returns the id the task tags its `WatchAnalysisFinished` with.
+pub fn reanalyze(path: String, build_variant: persistance::BuildVariant, file_cache: FileCache) -> (u64, Task<Message>) {
+    let id = NEXT_REANALYSIS_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = oneshot::channel();
+
+    std::thread::spawn(move || {
+        let result = crate::processing::load_path_with_progress(&path, &build_variant, file_cache, &AtomicBool::new(false), &|_| {});
+        let _ = sender.send(AnalysisOutcome::new(result));
+    });
+
+    let task = Task::perform(receiver, move |outcome| {
+        Message::WatchAnalysisFinished(
+            id,
+            outcome.unwrap_or_else(|_| AnalysisOutcome::new(Err(AnalysisError::Other("Watch thread stopped".to_string())))),
+        )
+    });
+
+    (id, task)
+}
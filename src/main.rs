@@ -1,13 +1,37 @@
+mod cli;
 mod gui;
 mod processing;
 mod model;
+use std::process::ExitCode;
+
 use crate::gui::App;
 
-fn main() -> iced::Result {
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Any command line argument switches to the headless mode
+    if !args.is_empty() {
+        // Keep stdout clean for the reports, log to stderr instead
+        tracing_subscriber::fmt()
+            .with_writer(std::io::stderr)
+            .init();
+
+        return cli::run(&args);
+    }
+
     tracing_subscriber::fmt::init();
 
-    iced::application(App::title, App::update, App::view)
+    let result = iced::application(App::title, App::update, App::view)
+        .subscription(App::subscription)
         .font(include_bytes!("../fonts/JetBrainsMono-Regular.ttf").as_slice())
         .centered()
-        .run_with(App::new)
-}
\ No newline at end of file
+        .run_with(App::new);
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,29 +1,68 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Copy, Clone, Debug)]
+// Configuration whose trace files are analyzed. Multi config generators put the trace files of each
+// configuration into a directory named after it below every `.dir` directory, so any name is valid.
+// Stored as a plain string, so that reports and the persistance written with the fixed list of
+// variants are still readable.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
 pub enum BuildVariant {
-    Debug,
-    DevRelease,
-    Release,
+    Config(String),
     SingleConfig,
 }
 
-impl BuildVariant {
-    pub const ALL: [BuildVariant; 4] = [
-        BuildVariant::Debug,
-        BuildVariant::DevRelease,
-        BuildVariant::Release,
-        BuildVariant::SingleConfig
-    ];
+impl Default for BuildVariant {
+    fn default() -> Self {
+        BuildVariant::Config("Release".to_string())
+    }
+}
+
+impl From<String> for BuildVariant {
+    fn from(name: String) -> Self {
+        if name.eq_ignore_ascii_case("SingleConfig") {
+            BuildVariant::SingleConfig
+        } else {
+            BuildVariant::Config(name)
+        }
+    }
+}
+
+impl From<BuildVariant> for String {
+    fn from(build_variant: BuildVariant) -> Self {
+        build_variant.to_string()
+    }
 }
 
 impl std::fmt::Display for BuildVariant {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            BuildVariant::Debug => write!(f, "Debug"),
-            BuildVariant::DevRelease => write!(f, "DevRelease"),
-            BuildVariant::Release => write!(f, "Release"),
+            BuildVariant::Config(name) => write!(f, "{}", name),
             BuildVariant::SingleConfig => write!(f, "SingleConfig"),
         }
     }
 }
+
+impl std::str::FromStr for BuildVariant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.contains(['/', '\\']) {
+            return Err(format!("Invalid build variant: {}", s));
+        }
+
+        Ok(BuildVariant::from(s.to_string()))
+    }
+}
+
+// Build variant found in a build directory, with the number of trace files it has
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredBuildVariant {
+    pub build_variant: BuildVariant,
+    pub trace_count: usize,
+}
+
+impl std::fmt::Display for DiscoveredBuildVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.build_variant, self.trace_count)
+    }
+}
@@ -0,0 +1,285 @@
+use serde::Deserialize;
+
+use super::comparison::{self, DiffEntry};
+use super::{AnalyisisResult, AnalysisError};
+
+// Limits checked by the `check` command. Every limit is optional, only the given ones are checked.
+// All times are in seconds.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Budget {
+    pub max_frontend_total_secs: Option<f64>,
+    pub max_backend_total_secs: Option<f64>,
+    pub max_user_time_secs: Option<f64>,
+    pub max_include_self_secs: Option<f64>,
+    pub max_include_total_secs: Option<f64>,
+    pub max_source_total_secs: Option<f64>,
+    pub max_target_wall_secs: Option<f64>,
+    pub max_frontend_operation_self_secs: Option<f64>,
+    pub max_backend_operation_secs: Option<f64>,
+
+    // Used only when comparing against a baseline: an item is a regression when it got slower by more
+    // than `max_regression_percent` percent and by more than `min_regression_secs` seconds
+    pub max_regression_percent: Option<f64>,
+    pub min_regression_secs: Option<f64>,
+}
+
+const DEFAULT_MAX_REGRESSION_PERCENT: f64 = 10.0;
+const DEFAULT_MIN_REGRESSION_SECS: f64 = 1.0;
+
+pub struct Violation {
+    pub rule: &'static str,
+    pub name: String,
+    pub value: String,
+    pub limit: String,
+}
+
+impl Budget {
+    pub fn from_path(path: &str) -> Result<Self, AnalysisError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AnalysisError::Other(format!("Failed to read {}: {}", path, e)))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| AnalysisError::Other(format!("Failed to parse budget {}: {}", path, e)))
+    }
+}
+
+fn secs(us: u64) -> f64 {
+    us as f64 * 1e-6
+}
+
+// Check a single value against an optional limit
+fn check_limit(violations: &mut Vec<Violation>, rule: &'static str, name: &str, value_secs: f64, limit_secs: Option<f64>) {
+    if let Some(limit_secs) = limit_secs {
+        if value_secs > limit_secs {
+            violations.push(Violation {
+                rule,
+                name: name.to_string(),
+                value: format!("{:.2} s", value_secs),
+                limit: format!("{:.2} s", limit_secs),
+            });
+        }
+    }
+}
+
+// Check the absolute limits of the budget against the analysis
+pub fn check_budget(analysis: &AnalyisisResult, budget: &Budget) -> Vec<Violation> {
+    let summary = &analysis.summary;
+
+    let mut violations = Vec::new();
+
+    check_limit(&mut violations, "max_frontend_total_secs", "Frontend", summary.frontend_duration_sec(), budget.max_frontend_total_secs);
+    check_limit(&mut violations, "max_backend_total_secs", "Backend", summary.backend_duration_sec(), budget.max_backend_total_secs);
+    check_limit(&mut violations, "max_user_time_secs", "User time", summary.inferred_used_time_secs(), budget.max_user_time_secs);
+
+    // Go over the sorted indices so that the worst offenders are reported first
+    for name in summary.frontend_file_largest_self_time_indices.iter() {
+        let include = &summary.frontend_file_process_summaries[name];
+        check_limit(&mut violations, "max_include_self_secs", name, secs(include.self_time_us), budget.max_include_self_secs);
+    }
+
+    for name in summary.frontend_file_largest_time_indices.iter() {
+        let include = &summary.frontend_file_process_summaries[name];
+        check_limit(&mut violations, "max_include_total_secs", name, secs(include.total_time_us), budget.max_include_total_secs);
+    }
+
+    for name in summary.source_file_largest_total_time_indices.iter() {
+        let source = &summary.source_file_process_summaries[name];
+        check_limit(&mut violations, "max_source_total_secs", name, secs(source.total_time_us), budget.max_source_total_secs);
+    }
+
+    for name in summary.target_summaries_largest_duration_indices.iter() {
+        let target = &summary.target_summaries[name];
+        let wall_secs = target.last_event_time.saturating_sub(target.first_event_time) as f64 * 1e-6;
+        check_limit(&mut violations, "max_target_wall_secs", name, wall_secs, budget.max_target_wall_secs);
+    }
+
+    for key in summary.frontend_operation_largest_self_time_indices.iter() {
+        let operation = &summary.frontend_operation_summaries[key];
+        let name = format!("{} {}", key.1.short_name(), key.0);
+        check_limit(&mut violations, "max_frontend_operation_self_secs", &name, secs(operation.self_time_us), budget.max_frontend_operation_self_secs);
+    }
+
+    for name in summary.backend_operation_largest_total_time_indices.iter() {
+        let operation = &summary.backend_operation_summaries[name];
+        check_limit(&mut violations, "max_backend_operation_secs", name, secs(operation.total_time_us), budget.max_backend_operation_secs);
+    }
+
+    violations
+}
+
+// Report every item which got slower than the baseline by more than the allowed regression
+pub fn check_regressions(baseline: &AnalyisisResult, analysis: &AnalyisisResult, budget: &Budget) -> Vec<Violation> {
+    let max_percent = budget.max_regression_percent.unwrap_or(DEFAULT_MAX_REGRESSION_PERCENT);
+    let min_secs = budget.min_regression_secs.unwrap_or(DEFAULT_MIN_REGRESSION_SECS);
+
+    let comparison = comparison::compare(&baseline.summary, &analysis.summary);
+
+    let mut violations = Vec::new();
+
+    let mut check_entries = |rule: &'static str, entries: &[DiffEntry<String>]| {
+        for entry in entries.iter().filter(|e| e.delta_us() > 0) {
+            let delta_secs = entry.delta_us() as f64 * 1e-6;
+
+            // Items which did not exist in the baseline have no percentage and only need to exceed the absolute limit
+            let exceeds_percent = entry.delta_percent().is_none_or(|p| p > max_percent);
+
+            if delta_secs > min_secs && exceeds_percent {
+                violations.push(Violation {
+                    rule,
+                    name: entry.key.clone(),
+                    value: format!(
+                        "+{:.2} s ({})",
+                        delta_secs,
+                        entry.delta_percent().map(|p| format!("{:+.1} %", p)).unwrap_or("new".to_string())
+                    ),
+                    limit: format!("{:.2} s / {:.1} %", min_secs, max_percent),
+                });
+            }
+        }
+    };
+
+    let totals = [
+        DiffEntry {
+            key: "Frontend".to_string(),
+            old_us: Some(baseline.summary.frontend_duration_total_us),
+            new_us: Some(analysis.summary.frontend_duration_total_us),
+        },
+        DiffEntry {
+            key: "Backend".to_string(),
+            old_us: Some(baseline.summary.backend_duration_total_us),
+            new_us: Some(analysis.summary.backend_duration_total_us),
+        },
+    ];
+
+    check_entries("total_regression", &totals);
+    check_entries("target_regression", &comparison.targets);
+    check_entries("include_regression", &comparison.includes);
+    check_entries("source_regression", &comparison.sources);
+    check_entries("backend_operation_regression", &comparison.backend_operations);
+
+    let frontend_operations: Vec<DiffEntry<String>> = comparison.frontend_operations
+        .into_iter()
+        .map(|e| DiffEntry {
+            key: format!("{} {}", e.key.1.short_name(), e.key.0),
+            old_us: e.old_us,
+            new_us: e.new_us,
+        })
+        .collect();
+
+    check_entries("frontend_operation_regression", &frontend_operations);
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::summary::{SourceFileProcessSummary, Summary};
+    use crate::processing::BuildVariant;
+
+    fn analysis(summary: Summary) -> AnalyisisResult {
+        AnalyisisResult {
+            selected_path: "/build".to_string(),
+            resolved_cmake_files_path: "/build/CMakeFiles".to_string(),
+            build_variant: BuildVariant::default(),
+            report_path: None,
+            summary,
+            file_cache: Default::default(),
+        }
+    }
+
+    fn sources(times: &[(&str, u64)]) -> AnalyisisResult {
+        let mut summary = Summary::default();
+
+        for (name, time_us) in times {
+            summary.source_file_process_summaries.insert(name.to_string(), SourceFileProcessSummary { total_time_us: *time_us, ..Default::default() });
+            summary.source_file_largest_total_time_indices.push(name.to_string());
+        }
+
+        analysis(summary)
+    }
+
+    fn rules(violations: &[Violation]) -> Vec<(&str, &str)> {
+        violations.iter().map(|v| (v.rule, v.name.as_str())).collect()
+    }
+
+    #[test]
+    fn limits_are_exclusive() {
+        let analysis = analysis(Summary {
+            frontend_duration_total_us: 2_000_000,
+            backend_duration_total_us: 3_000_000,
+            first_event_time: 1_000_000,
+            last_event_time: 5_000_000,
+            ..Default::default()
+        });
+
+        let at_limits = Budget {
+            max_frontend_total_secs: Some(2.0),
+            max_backend_total_secs: Some(3.0),
+            max_user_time_secs: Some(4.0),
+            ..Default::default()
+        };
+        assert!(check_budget(&analysis, &at_limits).is_empty());
+
+        let below_limits = Budget {
+            max_frontend_total_secs: Some(1.999),
+            max_backend_total_secs: Some(2.999),
+            max_user_time_secs: Some(3.999),
+            ..Default::default()
+        };
+        assert_eq!(
+            rules(&check_budget(&analysis, &below_limits)),
+            [("max_frontend_total_secs", "Frontend"), ("max_backend_total_secs", "Backend"), ("max_user_time_secs", "User time")],
+        );
+    }
+
+    #[test]
+    fn checks_every_item_against_its_limit() {
+        let analysis = sources(&[("slow.cpp", 1_500_000), ("limit.cpp", 1_000_000), ("fast.cpp", 200_000)]);
+
+        let budget = Budget { max_source_total_secs: Some(1.0), ..Default::default() };
+
+        assert_eq!(rules(&check_budget(&analysis, &budget)), [("max_source_total_secs", "slow.cpp")]);
+        assert!(check_budget(&analysis, &Budget::default()).is_empty());
+    }
+
+    #[test]
+    fn regressions_must_exceed_the_absolute_limit() {
+        let baseline = sources(&[("a.cpp", 10_000_000), ("b.cpp", 10_000_000)]);
+        let current = sources(&[("a.cpp", 12_000_000), ("b.cpp", 12_000_001)]);
+
+        let budget = Budget { max_regression_percent: Some(0.0), min_regression_secs: Some(2.0), ..Default::default() };
+
+        assert_eq!(rules(&check_regressions(&baseline, &current, &budget)), [("source_regression", "b.cpp")]);
+    }
+
+    #[test]
+    fn regressions_must_exceed_the_percent_limit() {
+        let baseline = sources(&[("a.cpp", 10_000_000), ("b.cpp", 10_000_000)]);
+        let current = sources(&[("a.cpp", 11_000_000), ("b.cpp", 11_000_001)]);
+
+        let budget = Budget { max_regression_percent: Some(10.0), min_regression_secs: Some(0.5), ..Default::default() };
+
+        assert_eq!(rules(&check_regressions(&baseline, &current, &budget)), [("source_regression", "b.cpp")]);
+    }
+
+    #[test]
+    fn new_items_only_need_to_exceed_the_absolute_limit() {
+        let baseline = sources(&[]);
+        let current = sources(&[("new.cpp", 1_000_001), ("small.cpp", 1_000_000)]);
+
+        let violations = check_regressions(&baseline, &current, &Budget::default());
+
+        assert_eq!(rules(&violations), [("source_regression", "new.cpp")]);
+        assert_eq!(violations[0].value, "+1.00 s (new)");
+    }
+
+    #[test]
+    fn improvements_are_not_regressions() {
+        let baseline = sources(&[("a.cpp", 10_000_000), ("gone.cpp", 5_000_000)]);
+        let current = sources(&[("a.cpp", 2_000_000)]);
+
+        assert!(check_regressions(&baseline, &current, &Budget::default()).is_empty());
+    }
+}
@@ -17,7 +17,7 @@ pub fn infer_cmake_cache_dir_path_from_source_dir_path(source_path: &str) -> Res
     let first_candidate = path.join("CMakeFiles");
 
     if first_candidate.exists() && first_candidate.is_dir() {
-        return Ok(first_candidate.to_str().unwrap().to_string());
+        return Ok(first_candidate.to_string_lossy().to_string());
     }
 
     // Check if the `build` directory exists
@@ -38,21 +38,25 @@ pub fn infer_cmake_cache_dir_path_from_source_dir_path(source_path: &str) -> Res
         let root_name = "linux";
 
         // List all directories in the `build` directory beginning with the root name
-        let mut candidates = std::fs::read_dir(&second_candidate)
-            .unwrap()
-            .map(|entry| entry.unwrap().path())
+        let Ok(entries) = std::fs::read_dir(&second_candidate) else {
+            return Err(CmakeCachePathInferenceError::CmakeCacheNotFound);
+        };
+
+        let mut candidates = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
             .filter(|path| path.is_dir())
-            .filter(|path| path.file_name().unwrap().to_str().unwrap().starts_with(root_name))
+            .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(root_name)))
             .collect::<Vec<PathBuf>>();
 
         // Sort the candidates by name
         candidates.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
 
-        // Check if the first candidate has a `CMakeFiles` directory
-        let first_candidate = candidates.first().unwrap().join("CMakeFiles");
-
-        if first_candidate.exists() && first_candidate.is_dir() {
-            return Ok(first_candidate.to_str().unwrap().to_string());
+        // Check if the first candidate has a `CMakeFiles` directory, a plain CMake `build` directory has none
+        if let Some(first_candidate) = candidates.first().map(|candidate| candidate.join("CMakeFiles")) {
+            if first_candidate.exists() && first_candidate.is_dir() {
+                return Ok(first_candidate.to_string_lossy().to_string());
+            }
         }
     }
 
@@ -64,4 +68,14 @@ pub enum CmakeCachePathInferenceError {
     SourceDirNotFound,
     BuildDirNotFound,
     CmakeCacheNotFound,
+}
+
+impl std::fmt::Display for CmakeCachePathInferenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CmakeCachePathInferenceError::SourceDirNotFound => write!(f, "source directory not found"),
+            CmakeCachePathInferenceError::BuildDirNotFound => write!(f, "build directory not found"),
+            CmakeCachePathInferenceError::CmakeCacheNotFound => write!(f, "`CMakeFiles` directory not found"),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use super::summary::{FrontendOperationKey, Summary};
+
+// Difference of a single item between the baseline and the current analysis.
+// A missing old value means that the item appeared, a missing new value means that it disappeared.
+pub struct DiffEntry<K> {
+    pub key: K,
+    pub old_us: Option<u64>,
+    pub new_us: Option<u64>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DiffStatus {
+    Changed,
+    Appeared,
+    Disappeared,
+}
+
+impl<K> DiffEntry<K> {
+    pub fn delta_us(&self) -> i64 {
+        self.new_us.unwrap_or_default() as i64 - self.old_us.unwrap_or_default() as i64
+    }
+
+    // Relative change in percent, only defined when the item existed in the baseline
+    pub fn delta_percent(&self) -> Option<f64> {
+        match self.old_us {
+            Some(old_us) if old_us > 0 => Some(self.delta_us() as f64 * 100.0 / old_us as f64),
+            _ => None,
+        }
+    }
+
+    pub fn status(&self) -> DiffStatus {
+        match (self.old_us, self.new_us) {
+            (None, _) => DiffStatus::Appeared,
+            (_, None) => DiffStatus::Disappeared,
+            _ => DiffStatus::Changed,
+        }
+    }
+}
+
+impl DiffStatus {
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            DiffStatus::Changed => "",
+            DiffStatus::Appeared => "NEW",
+            DiffStatus::Disappeared => "GONE",
+        }
+    }
+}
+
+// Per table differences, every vector is sorted by the delta so that the largest regression is first
+pub struct Comparison {
+    pub targets: Vec<DiffEntry<String>>,
+    pub includes: Vec<DiffEntry<String>>,
+    pub sources: Vec<DiffEntry<String>>,
+    pub frontend_operations: Vec<DiffEntry<FrontendOperationKey>>,
+    pub backend_operations: Vec<DiffEntry<String>>,
+}
+
+// Iterate the entries with the largest regression first, or with the largest improvement first
+pub fn ordered<K>(entries: &[DiffEntry<K>], improvements_first: bool) -> Box<dyn Iterator<Item = &DiffEntry<K>> + '_> {
+    if improvements_first {
+        Box::new(entries.iter().rev())
+    } else {
+        Box::new(entries.iter())
+    }
+}
+
+fn diff_maps<K: Ord + Clone, V>(
+    old: &BTreeMap<K, V>,
+    new: &BTreeMap<K, V>,
+    time_us: impl Fn(&V) -> u64,
+) -> Vec<DiffEntry<K>> {
+    let mut entries: Vec<DiffEntry<K>> = new
+        .iter()
+        .map(|(key, value)| DiffEntry {
+            key: key.clone(),
+            old_us: old.get(key).map(&time_us),
+            new_us: Some(time_us(value)),
+        })
+        .collect();
+
+    entries.extend(
+        old
+            .iter()
+            .filter(|(key, _)| !new.contains_key(key))
+            .map(|(key, value)| DiffEntry {
+                key: key.clone(),
+                old_us: Some(time_us(value)),
+                new_us: None,
+            })
+    );
+
+    // Largest regression first, stable so that equal deltas stay in key order
+    entries.sort_by_key(|e| std::cmp::Reverse(e.delta_us()));
+
+    entries
+}
+
+// Compare the total times of every table of the two summaries
+pub fn compare(baseline: &Summary, current: &Summary) -> Comparison {
+    Comparison {
+        targets: diff_maps(
+            &baseline.target_summaries,
+            &current.target_summaries,
+            |s| s.total_frontend_duration_us + s.total_backend_duration_us,
+        ),
+        includes: diff_maps(
+            &baseline.frontend_file_process_summaries,
+            &current.frontend_file_process_summaries,
+            |s| s.total_time_us,
+        ),
+        sources: diff_maps(
+            &baseline.source_file_process_summaries,
+            &current.source_file_process_summaries,
+            |s| s.total_time_us,
+        ),
+        frontend_operations: diff_maps(
+            &baseline.frontend_operation_summaries,
+            &current.frontend_operation_summaries,
+            |s| s.total_time_us,
+        ),
+        backend_operations: diff_maps(
+            &baseline.backend_operation_summaries,
+            &current.backend_operation_summaries,
+            |s| s.total_time_us,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::summary::{SourceFileProcessSummary, TopLevelTargetSummary};
+
+    fn entry(old_us: Option<u64>, new_us: Option<u64>) -> DiffEntry<String> {
+        DiffEntry { key: String::new(), old_us, new_us }
+    }
+
+    fn sources(times: &[(&str, u64)]) -> Summary {
+        let mut summary = Summary::default();
+
+        for (name, time_us) in times {
+            summary.source_file_process_summaries.insert(name.to_string(), SourceFileProcessSummary { total_time_us: *time_us, ..Default::default() });
+        }
+
+        summary
+    }
+
+    #[test]
+    fn computes_the_delta_and_status() {
+        let changed = entry(Some(2_000), Some(1_500));
+        assert_eq!(changed.delta_us(), -500);
+        assert_eq!(changed.delta_percent(), Some(-25.0));
+        assert_eq!(changed.status(), DiffStatus::Changed);
+
+        let appeared = entry(None, Some(700));
+        assert_eq!(appeared.delta_us(), 700);
+        assert_eq!(appeared.delta_percent(), None);
+        assert_eq!(appeared.status(), DiffStatus::Appeared);
+
+        let disappeared = entry(Some(300), None);
+        assert_eq!(disappeared.delta_us(), -300);
+        assert_eq!(disappeared.delta_percent(), Some(-100.0));
+        assert_eq!(disappeared.status(), DiffStatus::Disappeared);
+
+        // No percentage relative to an item which took no time in the baseline
+        assert_eq!(entry(Some(0), Some(100)).delta_percent(), None);
+    }
+
+    #[test]
+    fn sorts_the_largest_regression_first() {
+        let baseline = sources(&[("a.cpp", 100), ("b.cpp", 500), ("gone.cpp", 50)]);
+        let current = sources(&[("a.cpp", 400), ("b.cpp", 200), ("new.cpp", 300)]);
+
+        let comparison = compare(&baseline, &current);
+
+        let keys: Vec<&str> = comparison.sources.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["a.cpp", "new.cpp", "gone.cpp", "b.cpp"]);
+
+        let keys: Vec<&str> = ordered(&comparison.sources, true).map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["b.cpp", "gone.cpp", "new.cpp", "a.cpp"]);
+    }
+
+    #[test]
+    fn compares_targets_by_frontend_and_backend_time() {
+        let mut baseline = Summary::default();
+        baseline.target_summaries.insert("app".to_string(), TopLevelTargetSummary { total_frontend_duration_us: 100, total_backend_duration_us: 50, ..Default::default() });
+
+        let mut current = Summary::default();
+        current.target_summaries.insert("app".to_string(), TopLevelTargetSummary { total_frontend_duration_us: 80, total_backend_duration_us: 90, ..Default::default() });
+
+        let comparison = compare(&baseline, &current);
+
+        assert_eq!(comparison.targets.len(), 1);
+        assert_eq!(comparison.targets[0].old_us, Some(150));
+        assert_eq!(comparison.targets[0].new_us, Some(170));
+    }
+}
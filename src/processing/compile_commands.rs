@@ -0,0 +1,367 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use super::summary::Summary;
+use super::AnalysisError;
+
+// Entry of the compilation database, with the paths made absolute
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CompileCommand {
+    pub file: String,
+    pub output: String,
+    pub command: String,
+    // Options which change the generated code: optimization, debug info, defines, language and target options
+    pub flags: Vec<String>,
+}
+
+// Entry as written by CMake, either `command` or `arguments` is set and `output` is only written by newer versions
+#[derive(Deserialize)]
+struct DatabaseEntry {
+    directory: String,
+    file: String,
+    command: Option<String>,
+    arguments: Option<Vec<String>>,
+    output: Option<String>,
+}
+
+// Read the `compile_commands.json` of the build directory and map its entries to the sources of
+// the trace files, keyed like `Summary::source_file_process_summaries`. Clang writes the trace
+// file next to the object file, so they are joined by the output path.
+// Returns no entries when the build directory has no compilation database.
+pub fn read_compile_commands(
+    build_path: &Path,
+    trace_sources: &BTreeMap<PathBuf, String>,
+) -> Result<BTreeMap<String, CompileCommand>, AnalysisError> {
+    let database_path = build_path.join("compile_commands.json");
+
+    if !database_path.is_file() {
+        return Ok(BTreeMap::new());
+    }
+
+    let entries: Vec<DatabaseEntry> = std::fs::read_to_string(&database_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        .map_err(|e| AnalysisError::Other(format!("Failed to read {}: {}", database_path.display(), e)))?;
+
+    let commands = join_entries(entries, build_path, trace_sources);
+
+    trace!("Joined {} compile commands to trace files", commands.len());
+
+    Ok(commands)
+}
+
+fn join_entries(
+    entries: Vec<DatabaseEntry>,
+    build_path: &Path,
+    trace_sources: &BTreeMap<PathBuf, String>,
+) -> BTreeMap<String, CompileCommand> {
+    let mut commands = BTreeMap::new();
+
+    for entry in entries {
+        let arguments = match (&entry.arguments, &entry.command) {
+            (Some(arguments), _) => arguments.clone(),
+            (None, Some(command)) => split_command(command),
+            (None, None) => continue,
+        };
+
+        let command = entry.command.clone().unwrap_or_else(|| join_command(&arguments));
+
+        let output = entry.output.clone().or_else(|| output_argument(&arguments));
+
+        let Some(output) = output else {
+            continue;
+        };
+
+        // Relative directories are relative to the build directory
+        let directory = build_path.join(&entry.directory);
+        let output = directory.join(output);
+
+        let Some(source) = trace_sources.get(&super::canonical_path(&output.with_extension("json"))) else {
+            continue;
+        };
+
+        commands.insert(source.clone(), CompileCommand {
+            file: directory.join(&entry.file).to_string_lossy().to_string(),
+            output: output.to_string_lossy().to_string(),
+            command,
+            flags: notable_flags(&arguments),
+        });
+    }
+
+    commands
+}
+
+// Split a shell command line, honoring quotes and backslash escapes
+fn split_command(command: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', q) if q != Some('\'') => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+                in_argument = true;
+            }
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                in_argument = true;
+            }
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            (c, _) => {
+                current.push(c);
+                in_argument = true;
+            }
+        }
+    }
+
+    if in_argument {
+        arguments.push(current);
+    }
+
+    arguments
+}
+
+fn join_command(arguments: &[String]) -> String {
+    arguments
+        .iter()
+        .map(|argument| if argument.contains(char::is_whitespace) { format!("\"{}\"", argument) } else { argument.clone() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn output_argument(arguments: &[String]) -> Option<String> {
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        if argument == "-o" {
+            return arguments.next().cloned();
+        }
+
+        if let Some(output) = argument.strip_prefix("/Fo").or_else(|| argument.strip_prefix("-Fo")) {
+            return Some(output.to_string());
+        }
+    }
+
+    None
+}
+
+fn notable_flags(arguments: &[String]) -> Vec<String> {
+    const PREFIXES: [&str; 10] = ["-O", "-g", "-D", "-U", "-std=", "-f", "-m", "/O", "/D", "/std:"];
+
+    let mut flags = BTreeSet::new();
+    let mut arguments = arguments.iter().skip(1);
+
+    while let Some(argument) = arguments.next() {
+        // Defines given as a separate argument
+        if argument == "-D" || argument == "-U" {
+            if let Some(value) = arguments.next() {
+                flags.insert(format!("{}{}", argument, value));
+            }
+            continue;
+        }
+
+        // The trace itself is requested for every file, so it only adds noise
+        if argument.starts_with("-ftime-trace") {
+            continue;
+        }
+
+        if PREFIXES.iter().any(|prefix| argument.starts_with(prefix)) {
+            flags.insert(argument.clone());
+        }
+    }
+
+    flags.into_iter().collect()
+}
+
+// Translation units compiled with the same deviations from the flags most of them use
+#[derive(Default, Clone)]
+pub struct FlagGroup {
+    // Flags used by this group but not by most translation units
+    pub added: Vec<String>,
+    // Flags used by most translation units but not by this group
+    pub missing: Vec<String>,
+    // The most expensive first
+    pub sources: Vec<String>,
+    pub total_time_us: u64,
+}
+
+impl FlagGroup {
+    pub fn description(&self) -> String {
+        match (self.added.is_empty(), self.missing.is_empty()) {
+            (true, true) => "(common flags)".to_string(),
+            (false, true) => format!("with {}", self.added.join(" ")),
+            (true, false) => format!("without {}", self.missing.join(" ")),
+            (false, false) => format!("with {}, without {}", self.added.join(" "), self.missing.join(" ")),
+        }
+    }
+}
+
+// Group the translation units by the difference of their flags to the flags used by more than half
+// of them. The largest group comes first.
+pub fn compute_flag_groups(summary: &Summary) -> Vec<FlagGroup> {
+    let commands = &summary.compile_commands;
+
+    let mut flag_counts: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for command in commands.values() {
+        for flag in command.flags.iter() {
+            *flag_counts.entry(flag).or_default() += 1;
+        }
+    }
+
+    let common_flags: BTreeSet<&str> = flag_counts
+        .into_iter()
+        .filter(|(_, count)| count * 2 > commands.len())
+        .map(|(flag, _)| flag)
+        .collect();
+
+    let source_time = |source: &String| {
+        summary.source_file_process_summaries
+            .get(source)
+            .map(|s| s.total_time_us)
+            .unwrap_or_default()
+    };
+
+    let mut groups: BTreeMap<(Vec<String>, Vec<String>), FlagGroup> = BTreeMap::new();
+
+    for (source, command) in commands.iter() {
+        let flags: BTreeSet<&str> = command.flags.iter().map(|flag| flag.as_str()).collect();
+
+        let added: Vec<String> = flags.difference(&common_flags).map(|flag| flag.to_string()).collect();
+        let missing: Vec<String> = common_flags.difference(&flags).map(|flag| flag.to_string()).collect();
+
+        let group = groups.entry((added.clone(), missing.clone())).or_insert_with(|| FlagGroup {
+            added,
+            missing,
+            ..Default::default()
+        });
+
+        group.sources.push(source.clone());
+        group.total_time_us += source_time(source);
+    }
+
+    let mut groups: Vec<FlagGroup> = groups.into_values().collect();
+
+    for group in groups.iter_mut() {
+        group.sources.sort_by_key(|source| std::cmp::Reverse(source_time(source)));
+    }
+
+    groups.sort_by_key(|group| std::cmp::Reverse(group.sources.len()));
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::summary::SourceFileProcessSummary;
+
+    fn join(database: &str, trace_sources: &[(&str, &str)]) -> BTreeMap<String, CompileCommand> {
+        let entries: Vec<DatabaseEntry> = serde_json::from_str(database).unwrap();
+        let trace_sources = trace_sources.iter().map(|(path, source)| (PathBuf::from(path), source.to_string())).collect();
+
+        join_entries(entries, Path::new("/no/build"), &trace_sources)
+    }
+
+    #[test]
+    fn joins_command_entries() {
+        let commands = join(
+            r#"[{
+                "directory": "/no/build",
+                "command": "clang++ -DFOO=1 -O2 -g -Wall -ftime-trace -o CMakeFiles/app.dir/src/a.cpp.o -c /src/a.cpp",
+                "file": "/src/a.cpp"
+            }]"#,
+            &[("/no/build/CMakeFiles/app.dir/src/a.cpp.json", "src/a.cpp")],
+        );
+
+        let command = &commands["src/a.cpp"];
+
+        assert_eq!(command.file, "/src/a.cpp");
+        assert_eq!(command.output, "/no/build/CMakeFiles/app.dir/src/a.cpp.o");
+        assert!(command.command.starts_with("clang++ -DFOO=1"));
+        assert_eq!(command.flags, ["-DFOO=1", "-O2", "-g"]);
+    }
+
+    #[test]
+    fn joins_argument_entries() {
+        let commands = join(
+            r#"[{
+                "directory": "/no/build",
+                "arguments": ["clang++", "-D", "NAME=\"a b\"", "-std=c++20", "-c", "a.cpp"],
+                "file": "a.cpp",
+                "output": "CMakeFiles/app.dir/a.cpp.o"
+            }]"#,
+            &[("/no/build/CMakeFiles/app.dir/a.cpp.json", "a.cpp")],
+        );
+
+        let command = &commands["a.cpp"];
+
+        assert_eq!(command.command, r#"clang++ -D "NAME="a b"" -std=c++20 -c a.cpp"#);
+        assert_eq!(command.flags, ["-DNAME=\"a b\"", "-std=c++20"]);
+    }
+
+    #[test]
+    fn resolves_relative_paths_against_the_build_directory() {
+        let commands = join(
+            r#"[
+                {"directory": "sub", "command": "cc -O0 -o obj/b.c.o -c ../../src/b.c", "file": "../../src/b.c"},
+                {"directory": "/no/build", "command": "cc -O0 -o untraced.c.o -c untraced.c", "file": "untraced.c"},
+                {"directory": "/no/build", "file": "no_command.c"}
+            ]"#,
+            &[("/no/build/sub/obj/b.c.json", "src/b.c")],
+        );
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands["src/b.c"].file, "/no/build/sub/../../src/b.c");
+        assert_eq!(commands["src/b.c"].output, "/no/build/sub/obj/b.c.o");
+    }
+
+    #[test]
+    fn splits_quoted_commands() {
+        assert_eq!(
+            split_command(r#"cc -DA="x y" 'it'\''s' a\ b  -c"#),
+            ["cc", "-DA=x y", "it's", "a b", "-c"],
+        );
+    }
+
+    #[test]
+    fn groups_sources_by_their_deviating_flags() {
+        let mut summary = Summary::default();
+
+        for (source, flags, time_us) in [("a.cpp", "-O2 -g", 100), ("b.cpp", "-O2 -g", 300), ("c.cpp", "-O0 -g", 50)] {
+            summary.compile_commands.insert(source.to_string(), CompileCommand {
+                file: source.to_string(),
+                output: String::new(),
+                command: String::new(),
+                flags: flags.split(' ').map(|flag| flag.to_string()).collect(),
+            });
+
+            summary.source_file_process_summaries.insert(source.to_string(), SourceFileProcessSummary { total_time_us: time_us, ..Default::default() });
+        }
+
+        let groups = compute_flag_groups(&summary);
+
+        assert_eq!(groups.len(), 2);
+
+        assert_eq!(groups[0].description(), "(common flags)");
+        assert_eq!(groups[0].sources, ["b.cpp", "a.cpp"]);
+        assert_eq!(groups[0].total_time_us, 400);
+
+        assert_eq!(groups[1].description(), "with -O0, without -O2");
+        assert_eq!(groups[1].sources, ["c.cpp"]);
+    }
+}
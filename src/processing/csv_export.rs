@@ -0,0 +1,618 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use super::comparison::{self, DiffEntry};
+use super::file_summary::FileSummary;
+use super::namespace_tree::NamespaceNode;
+use super::summary::{EventKey, FrontendOperationKey, IncludeTreeNode, Summary};
+use super::timeline::Parallelism;
+use super::variant_comparison::{VariantComparison, VariantTable};
+use super::{AnalyisisResult, AnalysisError};
+use crate::model::BuildVariant;
+
+// Builds the CSV tables of the browsing panes. Every function takes the row order as a slice of keys so
+// that the caller can pass any of the sorted index vectors from the `Summary`.
+// Names are never truncated and times are written with full microsecond precision.
+
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn secs(us: u64) -> String {
+    format!("{:.6}", us as f64 * 1e-6)
+}
+
+fn avg_ms(us: u64, num: usize) -> String {
+    format!("{:.3}", us as f64 * 1e-3 / num as f64)
+}
+
+pub fn targets_csv(summary: &Summary, order: &[String]) -> String {
+    let mut out = String::from("Target,Total files,Frontend [s],Backend [s],Start [s],End [s]\n");
+
+    for target_name in order {
+        let target_summary = &summary.target_summaries[target_name];
+
+        // Targets with only invalid trace files have no event times
+        let relative_first_time = target_summary.first_event_time.saturating_sub(summary.first_event_time) as u64;
+        let relative_last_time = target_summary.last_event_time.saturating_sub(summary.first_event_time) as u64;
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            escape(target_name),
+            target_summary.total_files,
+            secs(target_summary.total_frontend_duration_us),
+            secs(target_summary.total_backend_duration_us),
+            secs(relative_first_time),
+            secs(relative_last_time),
+        );
+    }
+
+    out
+}
+
+pub fn includes_csv(summary: &Summary, order: &[String]) -> String {
+    let mut out = String::from("Include Path,#,Self [s],Total [s],AVG Self [ms],AVG Total [ms]\n");
+
+    for include in order {
+        let include_summary = &summary.frontend_file_process_summaries[include];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            escape(include),
+            include_summary.num,
+            secs(include_summary.self_time_us),
+            secs(include_summary.total_time_us),
+            avg_ms(include_summary.self_time_us, include_summary.num),
+            avg_ms(include_summary.total_time_us, include_summary.num),
+        );
+    }
+
+    out
+}
+
+// Every compile job with its lane on the build timeline, in start order
+pub fn compile_jobs_csv(summary: &Summary) -> String {
+    let mut out = String::from("Source Path,Target,Lane,Start [s],Duration [s]\n");
+
+    for (job, lane) in summary.compile_jobs.iter().zip(summary.timeline.lanes.iter()) {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            escape(&job.source),
+            escape(&job.target_name),
+            lane,
+            secs(job.start_time.saturating_sub(summary.first_event_time) as u64),
+            secs(job.duration_us),
+        );
+    }
+
+    out
+}
+
+pub fn parallelism_csv(parallelism: &Parallelism) -> String {
+    let mut out = String::from("Metric,Value\n");
+
+    let _ = writeln!(out, "CPU time [s],{}", secs(parallelism.cpu_time_us));
+    let _ = writeln!(out, "Wall time [s],{}", secs(parallelism.wall_time_us));
+    let _ = writeln!(out, "Average concurrency,{:.3}", parallelism.average_concurrency);
+    let _ = writeln!(out, "Peak concurrency,{}", parallelism.peak_concurrency);
+    let _ = writeln!(out, "Cores,{}", parallelism.cores);
+    let _ = writeln!(out, "Parallel efficiency [%],{:.2}", parallelism.parallel_efficiency * 100.0);
+
+    out
+}
+
+pub fn sequential_stretches_csv(parallelism: &Parallelism) -> String {
+    let mut out = String::from("Start [s],Duration [s]\n");
+
+    for stretch in parallelism.sequential_stretches.iter() {
+        let _ = writeln!(out, "{},{}", secs(stretch.start_us), secs(stretch.duration_us));
+    }
+
+    out
+}
+
+// Steps of the critical path in the given order of indices into `CriticalPath::steps`
+pub fn critical_path_csv(summary: &Summary, order: &[usize]) -> String {
+    let critical_path = &summary.critical_path;
+
+    let mut out = String::from("Step,Target,Duration [s],Share [%]\n");
+
+    for i in order {
+        let step = &critical_path.steps[*i];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{:.2}",
+            i + 1,
+            escape(&step.target_name),
+            secs(step.duration_us),
+            step.duration_us as f64 * 100.0 / critical_path.duration_us.max(1) as f64,
+        );
+    }
+
+    out
+}
+
+// Steps of the last ninja build in start order
+pub fn ninja_steps_csv(summary: &Summary) -> String {
+    let mut out = String::from("Kind,Target,Start [s],Duration [s],Source Path,Output\n");
+
+    for step in summary.ninja_steps.iter() {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            step.kind.short_name(),
+            escape(step.target_name.as_deref().unwrap_or("")),
+            secs(step.start_us),
+            secs(step.duration_us),
+            escape(step.source.as_deref().unwrap_or("")),
+            escape(&step.output),
+        );
+    }
+
+    out
+}
+
+pub fn target_steps_csv(summary: &Summary) -> String {
+    let mut out = String::from("Target,Compile [s],Link [s],Other [s],Total [s]\n");
+
+    for target_name in summary.ninja.target_steps_largest_total_indices.iter() {
+        let steps = &summary.ninja.target_steps[target_name];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            escape(target_name),
+            secs(steps.compile_us),
+            secs(steps.link_us),
+            secs(steps.other_us),
+            secs(steps.total_us()),
+        );
+    }
+
+    out
+}
+
+// Translation units which included `include`, the most expensive first
+pub fn including_translation_units_csv(summary: &Summary, include: &str) -> String {
+    let mut out = String::from("Source Path,Target,#,Self [s],Total [s]\n");
+
+    let Some(include_summary) = summary.frontend_file_process_summaries.get(include) else {
+        return out;
+    };
+
+    for translation_unit in include_summary.translation_units.iter() {
+        let _ = writeln!(
+            out,
+            "{},{},{},{:.6},{}",
+            escape(&translation_unit.source),
+            escape(&translation_unit.target_name),
+            translation_unit.num,
+            translation_unit.self_time_us as f64 * 1e-6,
+            secs(translation_unit.total_time_us),
+        );
+    }
+
+    out
+}
+
+pub fn sources_csv(summary: &Summary, order: &[String]) -> String {
+    let mut out = String::from("Source Path,Total [s],Front [s],Back [s],Source File,Output,Command\n");
+
+    for source in order {
+        let source_summary = &summary.source_file_process_summaries[source];
+
+        // Empty when the build directory has no `compile_commands.json`
+        let (file, output, command) = summary.compile_commands
+            .get(source)
+            .map(|c| (c.file.as_str(), c.output.as_str(), c.command.as_str()))
+            .unwrap_or_default();
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            escape(source),
+            secs(source_summary.total_time_us),
+            secs(source_summary.total_frontend_time_us),
+            secs(source_summary.total_backend_time_us),
+            escape(file),
+            escape(output),
+            escape(command),
+        );
+    }
+
+    out
+}
+
+// One row per translation unit, in the order of the groups
+pub fn flag_groups_csv(summary: &Summary) -> String {
+    let mut out = String::from("Group,Flags,Group Size,Group Total [s],Source Path,Total [s]\n");
+
+    for (i, group) in summary.flag_groups.iter().enumerate() {
+        for source in group.sources.iter() {
+            let total_time_us = summary.source_file_process_summaries
+                .get(source)
+                .map(|s| s.total_time_us)
+                .unwrap_or_default();
+
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                i + 1,
+                escape(&group.description()),
+                group.sources.len(),
+                secs(group.total_time_us),
+                escape(source),
+                secs(total_time_us),
+            );
+        }
+    }
+
+    out
+}
+
+pub fn frontend_operations_csv(summary: &Summary, order: &[FrontendOperationKey]) -> String {
+    let mut out = String::from("Type,Name,#,Self [s],Total [s],AVG Self [ms],AVG Total [ms]\n");
+
+    for key in order {
+        let operation_summary = &summary.frontend_operation_summaries[key];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            key.1.short_name(),
+            escape(&key.0),
+            operation_summary.num,
+            secs(operation_summary.self_time_us),
+            secs(operation_summary.total_time_us),
+            avg_ms(operation_summary.self_time_us, operation_summary.num),
+            avg_ms(operation_summary.total_time_us, operation_summary.num),
+        );
+    }
+
+    out
+}
+
+pub fn events_csv(summary: &Summary, order: &[EventKey]) -> String {
+    let mut out = String::from("Event,Detail,#,Self [s],Total [s],AVG Self [ms],AVG Total [ms]\n");
+
+    for key in order {
+        let event_summary = &summary.event_summaries[key];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            escape(&key.0),
+            escape(&key.1),
+            event_summary.num,
+            secs(event_summary.self_time_us),
+            secs(event_summary.total_time_us),
+            avg_ms(event_summary.self_time_us, event_summary.num),
+            avg_ms(event_summary.total_time_us, event_summary.num),
+        );
+    }
+
+    out
+}
+
+pub fn event_names_csv(summary: &Summary, order: &[String]) -> String {
+    let mut out = String::from("Event,#,Self [s],Total [s],AVG Self [ms],AVG Total [ms]\n");
+
+    for name in order {
+        let event_summary = &summary.event_name_summaries[name];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            escape(name),
+            event_summary.num,
+            secs(event_summary.self_time_us),
+            secs(event_summary.total_time_us),
+            avg_ms(event_summary.self_time_us, event_summary.num),
+            avg_ms(event_summary.total_time_us, event_summary.num),
+        );
+    }
+
+    out
+}
+
+pub fn passes_csv(summary: &Summary, order: &[String]) -> String {
+    let mut out = String::from("Pass,#,Self [s],Total [s],AVG Self [ms],AVG Total [ms]\n");
+
+    for name in order {
+        let pass_summary = &summary.pass_summaries[name];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            escape(name),
+            pass_summary.num,
+            secs(pass_summary.self_time_us),
+            secs(pass_summary.total_time_us),
+            avg_ms(pass_summary.self_time_us, pass_summary.num),
+            avg_ms(pass_summary.total_time_us, pass_summary.num),
+        );
+    }
+
+    out
+}
+
+pub fn template_groups_csv(summary: &Summary, order: &[FrontendOperationKey]) -> String {
+    let mut out = String::from("Type,Template,Instantiations,#,Self [s],Total [s],AVG Self [ms],AVG Total [ms],Most expensive instantiation\n");
+
+    for key in order {
+        let group = &summary.template_groups[key];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            key.1.short_name(),
+            escape(&key.0),
+            group.instantiations.len(),
+            group.num,
+            secs(group.self_time_us),
+            secs(group.total_time_us),
+            avg_ms(group.self_time_us, group.num),
+            avg_ms(group.total_time_us, group.num),
+            escape(group.instantiations.first().map(|name| name.as_str()).unwrap_or_default()),
+        );
+    }
+
+    out
+}
+
+pub fn backend_operations_csv(summary: &Summary, order: &[String]) -> String {
+    let mut out = String::from("Name,#,Total [s],AVG Total [ms]\n");
+
+    for key in order {
+        let operation_summary = &summary.backend_operation_summaries[key];
+
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            escape(key),
+            operation_summary.num,
+            secs(operation_summary.total_time_us),
+            avg_ms(operation_summary.total_time_us, operation_summary.num),
+        );
+    }
+
+    out
+}
+
+// Everything a single translation unit contributed: its includes, frontend and backend operations.
+// The kind is `Include`, the short name of the frontend operation or `Backend`.
+pub fn translation_unit_csv(file: &FileSummary) -> String {
+    let mut out = String::from("Kind,Name,#,Self [s],Total [s]\n");
+
+    for (name, include) in file.includes.iter() {
+        let _ = writeln!(
+            out,
+            "Include,{},{},{},{}",
+            escape(name),
+            include.num,
+            secs(include.self_time_us),
+            secs(include.total_time_us),
+        );
+    }
+
+    for (key, operation) in file.frontend_operations.iter() {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            key.1.short_name(),
+            escape(&key.0),
+            operation.num,
+            secs(operation.self_time_us),
+            secs(operation.total_time_us),
+        );
+    }
+
+    for (name, operation) in file.backend_operations.iter() {
+        let _ = writeln!(
+            out,
+            "Backend,{},{},,{}",
+            escape(name),
+            operation.num,
+            secs(operation.total_time_us),
+        );
+    }
+
+    for (name, pass) in file.passes.iter() {
+        let _ = writeln!(
+            out,
+            "Pass,{},{},{},{}",
+            escape(name),
+            pass.num,
+            secs(pass.self_time_us),
+            secs(pass.total_time_us),
+        );
+    }
+
+    out
+}
+
+// One row per node of the include hierarchy, depth first with the most expensive child first.
+// The chain lists the names from the top level down to the node, separated by ` > `.
+pub fn include_tree_csv<'a>(top_level: impl IntoIterator<Item = (&'a String, &'a IncludeTreeNode)>) -> String {
+    fn write_node(out: &mut String, chain: &str, node: &IncludeTreeNode) {
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            escape(chain),
+            node.num,
+            secs(node.self_time_us),
+            secs(node.total_time_us),
+        );
+
+        for (name, child) in node.sorted_children() {
+            write_node(out, &format!("{} > {}", chain, name), child);
+        }
+    }
+
+    let mut out = String::from("Include Chain,#,Self [s],Total [s]\n");
+
+    for (name, node) in top_level {
+        write_node(&mut out, name, node);
+    }
+
+    out
+}
+
+pub fn namespace_tree_csv(node: &NamespaceNode, time_us: impl Fn(&NamespaceNode) -> u64 + Copy) -> String {
+    fn write_node(out: &mut String, path: &str, node: &NamespaceNode, time_us: impl Fn(&NamespaceNode) -> u64 + Copy) {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            escape(path),
+            node.num,
+            secs(node.frontend_time_us),
+            secs(node.backend_time_us),
+            secs(node.total_time_us()),
+        );
+
+        for (name, child) in node.sorted_children(time_us) {
+            write_node(out, &format!("{}::{}", path, name), child, time_us);
+        }
+    }
+
+    let mut out = String::from("Namespace,#,Frontend Self [s],Backend [s],Total [s]\n");
+
+    for (name, child) in node.sorted_children(time_us) {
+        write_node(&mut out, name, child, time_us);
+    }
+
+    out
+}
+
+pub fn write_csv(path: &Path, content: &str) -> Result<(), AnalysisError> {
+    std::fs::write(path, content)
+        .map_err(|e| AnalysisError::Other(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+// Write every table into `dir`, using the default order of each pane. Returns the written paths.
+pub fn write_all_csv(analysis: &AnalyisisResult, dir: &Path) -> Result<Vec<String>, AnalysisError> {
+    let summary = &analysis.summary;
+
+    std::fs::create_dir_all(dir)
+        .map_err(|e| AnalysisError::Other(format!("Failed to create {}: {}", dir.display(), e)))?;
+
+    let tables = [
+        ("targets.csv", targets_csv(summary, &summary.target_summaries_alpha_order)),
+        ("includes.csv", includes_csv(summary, &summary.frontend_file_largest_time_indices)),
+        ("sources.csv", sources_csv(summary, &summary.source_file_largest_total_time_indices)),
+        ("flag_groups.csv", flag_groups_csv(summary)),
+        ("frontend.csv", frontend_operations_csv(summary, &summary.frontend_operation_largest_total_time_indices)),
+        ("templates.csv", template_groups_csv(summary, &summary.template_group_largest_total_time_indices)),
+        ("backend.csv", backend_operations_csv(summary, &summary.backend_operation_largest_total_time_indices)),
+        ("passes.csv", passes_csv(summary, &summary.pass_largest_self_time_indices)),
+        ("events.csv", events_csv(summary, &summary.event_largest_total_time_indices)),
+        ("event_names.csv", event_names_csv(summary, &summary.event_name_largest_total_time_indices)),
+        ("namespaces.csv", namespace_tree_csv(&summary.namespace_tree, |n| n.total_time_us())),
+        ("include_tree.csv", include_tree_csv(summary.include_tree.sorted_children())),
+        ("compile_jobs.csv", compile_jobs_csv(summary)),
+        ("critical_path.csv", critical_path_csv(summary, &(0..summary.critical_path.steps.len()).collect::<Vec<_>>())),
+        ("ninja_steps.csv", ninja_steps_csv(summary)),
+        ("target_steps.csv", target_steps_csv(summary)),
+        ("parallelism.csv", parallelism_csv(&summary.parallelism)),
+        ("sequential_stretches.csv", sequential_stretches_csv(&summary.parallelism)),
+    ];
+
+    let mut written = Vec::new();
+
+    for (file_name, content) in tables {
+        let path = dir.join(file_name);
+        write_csv(&path, &content)?;
+        written.push(path.to_string_lossy().to_string());
+    }
+
+    Ok(written)
+}
+
+pub fn comparison_csv<K>(entries: &[DiffEntry<K>], name: impl Fn(&K) -> String, improvements_first: bool) -> String {
+    let mut out = String::from("Name,Old [s],New [s],Delta [s],Delta [%],Status\n");
+
+    for entry in comparison::ordered(entries, improvements_first) {
+        let _ = writeln!(
+            out,
+            "{},{},{},{:.6},{},{}",
+            escape(&name(&entry.key)),
+            entry.old_us.map(secs).unwrap_or_default(),
+            entry.new_us.map(secs).unwrap_or_default(),
+            entry.delta_us() as f64 * 1e-6,
+            entry.delta_percent().map(|p| format!("{:.3}", p)).unwrap_or_default(),
+            entry.status().short_name(),
+        );
+    }
+
+    out
+}
+
+// Per configuration times, then the ratio of every further configuration to the first one
+pub fn variant_comparison_csv<K>(
+    build_variants: &[BuildVariant],
+    table: &VariantTable<K>,
+    name: impl Fn(&K) -> String,
+    by_ratio: bool,
+) -> String {
+    let mut out = String::from("Name");
+
+    for build_variant in build_variants {
+        let _ = write!(out, ",{} [s]", escape(&build_variant.to_string()));
+    }
+
+    for build_variant in build_variants.iter().skip(1) {
+        let _ = write!(out, ",{}/{}", escape(&build_variant.to_string()), escape(&build_variants[0].to_string()));
+    }
+
+    out.push('\n');
+
+    for entry in table.ordered(by_ratio) {
+        out.push_str(&escape(&name(&entry.key)));
+
+        for time_us in entry.times_us.iter() {
+            let _ = write!(out, ",{}", time_us.map(secs).unwrap_or_default());
+        }
+
+        for index in 1..entry.times_us.len() {
+            let _ = write!(out, ",{}", entry.ratio(index).map(|r| format!("{:.3}", r)).unwrap_or_default());
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+// Write every table of the configuration comparison into `dir`, ordered by the largest time
+pub fn write_variant_comparison_csv(comparison: &VariantComparison, dir: &Path) -> Result<Vec<String>, AnalysisError> {
+    let build_variants = &comparison.build_variants;
+
+    std::fs::create_dir_all(dir)
+        .map_err(|e| AnalysisError::Other(format!("Failed to create {}: {}", dir.display(), e)))?;
+
+    let tables = [
+        ("targets_variants.csv", variant_comparison_csv(build_variants, &comparison.targets, |k| k.clone(), false)),
+        ("includes_variants.csv", variant_comparison_csv(build_variants, &comparison.includes, |k| k.clone(), false)),
+        ("sources_variants.csv", variant_comparison_csv(build_variants, &comparison.sources, |k| k.clone(), false)),
+        ("frontend_variants.csv", variant_comparison_csv(build_variants, &comparison.frontend_operations, |k| format!("{} {}", k.1.short_name(), k.0), false)),
+        ("backend_variants.csv", variant_comparison_csv(build_variants, &comparison.backend_operations, |k| k.clone(), false)),
+    ];
+
+    let mut written = Vec::new();
+
+    for (file_name, content) in tables {
+        let path = dir.join(file_name);
+        write_csv(&path, &content)?;
+        written.push(path.to_string_lossy().to_string());
+    }
+
+    Ok(written)
+}
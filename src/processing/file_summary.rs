@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::summary::{
+    BackendOperationSummaries,
+    FrontendFileProcessSummary,
+    FrontendOperationSummaries,
+    IncludeTreeNode,
+    CompileJob,
+    EventSummaries,
+    EventSummary,
+    IncludingTranslationUnit,
+    Summary,
+};
+
+// Everything a single trace file contributes to the `Summary`.
+//
+// Trace files are processed independently (and in parallel) into a `FileSummary` each, which are then
+// merged into the `Summary` one after another in enumeration order, so the result does not depend on
+// the order in which the files were processed.
+#[derive(Default, Clone)]
+pub struct FileSummary {
+    pub target_name: String,
+    pub readable_path: String,
+    pub trace_path: PathBuf,
+
+    // Invalid files only count towards the number of invalid files, all other fields are empty
+    pub is_valid: bool,
+
+    pub beginning_of_time: u128,
+    pub frontend_duration_us: u64,
+    pub backend_duration_us: u64,
+    pub backend_duration_single_events_us: u64,
+    // Frontend and backend time during which no source, frontend or backend operation was recorded
+    pub uncovered_duration_us: u64,
+
+    pub includes: BTreeMap<String, FrontendFileProcessSummary>,
+    pub frontend_operations: FrontendOperationSummaries,
+    pub backend_operations: BTreeMap<String, BackendOperationSummaries>,
+    // Every event with a duration, whatever its name
+    pub events: EventSummaries,
+    // LLVM passes by their name
+    pub passes: BTreeMap<String, EventSummary>,
+
+    // The root is the translation unit itself, with the frontend time as its total time
+    pub include_tree: IncludeTreeNode,
+}
+
+impl FileSummary {
+    pub fn end_of_time(&self) -> u128 {
+        self.beginning_of_time + self.frontend_duration_us as u128 + self.backend_duration_us as u128
+    }
+}
+
+// Contribution of a trace file as it was when it was last parsed
+pub struct CachedFileSummary {
+    pub size: u64,
+    pub modified: SystemTime,
+    // `None` when the file could not be read
+    pub summary: Option<Arc<FileSummary>>,
+}
+
+// Parsed trace files of the previous analysis keyed by their path, used to re-parse only
+// the files which changed since then
+#[derive(Default)]
+pub struct FileCache {
+    pub files: BTreeMap<PathBuf, CachedFileSummary>,
+}
+
+impl FileCache {
+    // Cached contribution of the file, if it has the same size and modification time as before
+    pub fn get(&self, path: &PathBuf, size: u64, modified: SystemTime) -> Option<&Option<Arc<FileSummary>>> {
+        self.files
+            .get(path)
+            .filter(|c| c.size == size && c.modified == modified)
+            .map(|c| &c.summary)
+    }
+}
+
+// Add the contribution of a single file to the summary. The sorted indices are not updated.
+pub fn merge_file_summary(summary: &mut Summary, file: &Arc<FileSummary>) {
+    let target_summary = summary
+        .target_summaries
+        .entry(file.target_name.clone())
+        .or_default();
+
+    if !file.is_valid {
+        summary.total_invalid_files += 1;
+        return;
+    }
+
+    summary.total_valid_files += 1;
+    target_summary.total_files += 1;
+
+    if summary.first_event_time == 0 {
+        summary.first_event_time = file.beginning_of_time;
+    } else {
+        summary.first_event_time = summary.first_event_time.min(file.beginning_of_time);
+    }
+
+    let my_end_of_time = file.end_of_time();
+
+    summary.last_event_time = summary.last_event_time.max(my_end_of_time);
+
+    summary.frontend_duration_total_us += file.frontend_duration_us;
+    summary.backend_duration_total_us += file.backend_duration_us;
+    summary.backend_duration_single_events_us += file.backend_duration_single_events_us;
+
+    target_summary.total_frontend_duration_us += file.frontend_duration_us;
+    target_summary.total_backend_duration_us += file.backend_duration_us;
+
+    if target_summary.first_event_time == 0 {
+        target_summary.first_event_time = file.beginning_of_time;
+    } else {
+        target_summary.first_event_time = target_summary.first_event_time.min(file.beginning_of_time);
+    }
+
+    target_summary.last_event_time = target_summary.last_event_time.max(my_end_of_time);
+
+    for (name, include) in file.includes.iter() {
+        let include_summary = summary
+            .frontend_file_process_summaries
+            .entry(name.clone())
+            .or_default();
+
+        include_summary.num += include.num;
+        include_summary.total_time_us += include.total_time_us;
+        include_summary.self_time_us += include.self_time_us;
+
+        include_summary.translation_units.push(IncludingTranslationUnit {
+            source: file.readable_path.clone(),
+            target_name: file.target_name.clone(),
+            total_time_us: include.total_time_us,
+            self_time_us: include.self_time_us,
+            num: include.num,
+        });
+    }
+
+    let source_file_summary = summary.source_file_process_summaries
+        .entry(file.readable_path.clone())
+        .or_default();
+
+    source_file_summary.total_time_us = file.frontend_duration_us + file.backend_duration_us;
+    source_file_summary.total_frontend_time_us = file.frontend_duration_us;
+    source_file_summary.total_backend_time_us = file.backend_duration_us;
+
+    for (key, operation) in file.frontend_operations.iter() {
+        let operation_summary = summary
+            .frontend_operation_summaries
+            .entry(key.clone())
+            .or_default();
+
+        operation_summary.num += operation.num;
+        operation_summary.total_time_us += operation.total_time_us;
+        operation_summary.self_time_us += operation.self_time_us;
+    }
+
+    for (name, operation) in file.backend_operations.iter() {
+        let operation_summary = summary
+            .backend_operation_summaries
+            .entry(name.clone())
+            .or_default();
+
+        operation_summary.num += operation.num;
+        operation_summary.total_time_us += operation.total_time_us;
+    }
+
+    for (key, event) in file.events.iter() {
+        summary.event_summaries.entry(key.clone()).or_default().add(event);
+    }
+
+    for (name, pass) in file.passes.iter() {
+        summary.pass_summaries.entry(name.clone()).or_default().add(pass);
+    }
+
+    summary.compile_jobs.push(CompileJob {
+        source: file.readable_path.clone(),
+        target_name: file.target_name.clone(),
+        start_time: file.beginning_of_time,
+        duration_us: file.frontend_duration_us + file.backend_duration_us,
+    });
+
+    summary.include_tree.merge(&file.include_tree);
+
+    summary.translation_units.insert(file.readable_path.clone(), file.clone());
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{demangle_backend_name, trace_format, AnalysisError};
+
+// A single event of the trace, placed on the row given by its nesting depth
+pub struct FlameChartEvent {
+    pub name: String,
+    pub detail: Option<String>,
+    // Relative to the first event of the trace
+    pub start_us: u64,
+    pub duration_us: u64,
+    pub depth: usize,
+}
+
+// All timed events of a single trace file, sorted by their start time
+pub struct FlameChart {
+    pub events: Vec<FlameChartEvent>,
+    pub duration_us: u64,
+    pub max_depth: usize,
+}
+
+// Read the trace file again and lay out its events. An event is nested below the innermost event
+// which is still running when it starts.
+pub fn load_flame_chart(path: &Path) -> Result<FlameChart, AnalysisError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AnalysisError::Other(format!("Failed to read {}: {}", path.display(), e)))?;
+
+    let parsed = serde_json::from_str::<trace_format::Profile>(&content)
+        .map_err(|e| AnalysisError::Other(format!("Failed to parse {}: {}", path.display(), e)))?;
+
+    // The `Total ...` events are per name sums which are not placed in time
+    let mut events: Vec<&trace_format::Event> = parsed.traceEvents
+        .iter()
+        .filter(|e| e.dur.is_some() && !e.name.starts_with("Total "))
+        .collect();
+
+    // On equal timestamps the longer event is the parent
+    events.sort_by_key(|e| (e.ts, std::cmp::Reverse(e.dur.unwrap_or_default())));
+
+    let first_ts = events.first().map(|e| e.ts).unwrap_or_default();
+
+    let mut demangle_cache = BTreeMap::new();
+
+    let mut chart = FlameChart {
+        events: Vec::with_capacity(events.len()),
+        duration_us: 0,
+        max_depth: 0,
+    };
+
+    // End times of the currently open events
+    let mut stack: Vec<u64> = Vec::new();
+
+    for event in events {
+        let duration_us = event.dur.unwrap_or_default();
+        let start_us = event.ts - first_ts;
+        let end_us = start_us + duration_us;
+
+        while stack.last().is_some_and(|parent_end_us| *parent_end_us < end_us) {
+            stack.pop();
+        }
+
+        let detail = event.args
+            .as_ref()
+            .and_then(|a| a.detail.as_ref())
+            .map(|d| {
+                if d.trim_start_matches('(').starts_with("_Z") {
+                    demangle_backend_name(&mut demangle_cache, d)
+                } else {
+                    d.clone()
+                }
+            });
+
+        chart.max_depth = chart.max_depth.max(stack.len());
+        chart.duration_us = chart.duration_us.max(end_us);
+
+        chart.events.push(FlameChartEvent {
+            name: event.name.to_string(),
+            detail,
+            start_us,
+            duration_us,
+            depth: stack.len(),
+        });
+
+        stack.push(end_us);
+    }
+
+    Ok(chart)
+}
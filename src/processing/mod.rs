@@ -1,29 +1,107 @@
 mod trace_format;
 mod build_path_inference;
-
+pub mod file_summary;
+
+pub mod budget;
+pub mod comparison;
+pub mod compile_commands;
+pub mod csv_export;
+pub mod flame_chart;
+pub mod name_filter;
+pub mod namespace_tree;
+pub mod ninja_log;
+pub mod report;
 pub mod summary;
+pub mod target_graph;
+pub mod template_groups;
+pub mod timeline;
+pub mod variant_comparison;
 
 use std::{collections::BTreeMap, path::{Path, PathBuf}};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use build_path_inference::CmakeCachePathInferenceError;
 use cpp_demangle::{DemangleOptions, Symbol};
+use file_summary::{CachedFileSummary, FileSummary};
+use rayon::prelude::*;
 use tracing::{error, trace};
 
-use crate::model::BuildVariant;
+use crate::model::{BuildVariant, DiscoveredBuildVariant};
 use summary::{FrontendOperation, FrontendOperationKey, Summary};
 
+pub use file_summary::FileCache;
+
 pub struct AnalyisisResult {
     pub selected_path: String,
     pub resolved_cmake_files_path: String,
     pub build_variant: BuildVariant,
+    // Set when the analysis was loaded from a saved JSON report instead of a build tree
+    pub report_path: Option<String>,
     pub summary: Summary,
+    // Parsed trace files, reused by the next analysis of the same path (empty for reports)
+    file_cache: FileCache,
+}
+
+impl AnalyisisResult {
+    // Directory where exported files are written by default: the selected build directory,
+    // or the directory of the report when the analysis was loaded from one
+    pub fn export_dir(&self) -> PathBuf {
+        match &self.report_path {
+            Some(report_path) => Path::new(report_path)
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default(),
+            None => PathBuf::from(&self.selected_path),
+        }
+    }
+
+    // Path which needs to be opened again to refresh this analysis
+    pub fn reload_path(&self) -> &str {
+        self.report_path.as_deref().unwrap_or(&self.selected_path)
+    }
+
+    // Move the parsed trace files out, so that they can be passed to the next `analyze_path`
+    pub fn take_file_cache(&mut self) -> FileCache {
+        std::mem::take(&mut self.file_cache)
+    }
 }
 
 #[derive(Debug)]
 pub enum AnalysisError {
     InvalidPath(CmakeCachePathInferenceError),
+    InvalidReport(String),
+    Cancelled,
     Other(String),
 }
 
+// Progress of a running analysis, reported after the enumeration and after every processed file
+#[derive(Default, Clone, Copy, Debug)]
+pub struct AnalysisProgress {
+    pub files_enumerated: usize,
+    pub files_parsed: usize,
+    // Files which did not change since the previous analysis and were taken from the cache
+    pub files_reused: usize,
+    pub invalid_files: usize,
+}
+
+impl AnalysisProgress {
+    // Files which are done, whether they were parsed or reused
+    pub fn files_processed(&self) -> usize {
+        self.files_parsed + self.files_reused
+    }
+}
+
+impl std::fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisError::InvalidPath(e) => write!(f, "Invalid path: {}", e),
+            AnalysisError::InvalidReport(e) => write!(f, "Invalid report: {}", e),
+            AnalysisError::Cancelled => write!(f, "Cancelled"),
+            AnalysisError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
 struct ProfileLocationiInfo {
     path: PathBuf,
     relative_path: String,
@@ -96,6 +174,84 @@ fn enumerate_all_trace_files_for_multi_config(cmake_file_dir_path: &str, build_v
     Ok(trace_files)
 }
 
+// Find the build variants with trace files in the build directory, the configurations first and
+// `SingleConfig` (all trace files below the `.dir` directories) last.
+//
+// The configurations are the directories below the `.dir` directories which are listed in the
+// `CMAKE_CONFIGURATION_TYPES` of `CMakeCache.txt`. Single config generators do not set it, their
+// directories below `.dir` are source directories, so only `SingleConfig` is returned for them.
+pub fn discover_build_variants(selected_path: &str) -> Vec<DiscoveredBuildVariant> {
+    let Ok(cmake_file_dir_path) = build_path_inference::infer_cmake_cache_dir_path_from_source_dir_path(selected_path) else {
+        return Vec::new();
+    };
+
+    let configuration_types = configuration_types(Path::new(&cmake_file_dir_path));
+
+    let Ok(top_entries) = std::fs::read_dir(&cmake_file_dir_path) else {
+        return Vec::new();
+    };
+
+    let is_trace_file = |entry: &walkdir::DirEntry| {
+        entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "json")
+    };
+
+    let mut trace_counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut total_trace_count = 0;
+
+    for top_entry_path in top_entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if top_entry_path.extension().unwrap_or_default() != "dir" || !top_entry_path.is_dir() {
+            continue;
+        }
+
+        for entry in walkdir::WalkDir::new(&top_entry_path).into_iter().filter_map(|e| e.ok()).filter(is_trace_file) {
+            total_trace_count += 1;
+
+            // The first directory below the `.dir` directory
+            let first_dir = entry.path()
+                .strip_prefix(&top_entry_path)
+                .ok()
+                .filter(|relative| relative.components().count() > 1)
+                .and_then(|relative| relative.components().next())
+                .map(|component| component.as_os_str().to_string_lossy().to_string());
+
+            if let Some(first_dir) = first_dir {
+                *trace_counts.entry(first_dir).or_default() += 1;
+            }
+        }
+    }
+
+    let mut build_variants: Vec<DiscoveredBuildVariant> = trace_counts
+        .into_iter()
+        .filter(|(name, _)| configuration_types.as_ref().is_some_and(|types| types.contains(name)))
+        .map(|(name, trace_count)| DiscoveredBuildVariant {
+            build_variant: BuildVariant::Config(name),
+            trace_count,
+        })
+        .collect();
+
+    if total_trace_count > 0 {
+        build_variants.push(DiscoveredBuildVariant {
+            build_variant: BuildVariant::SingleConfig,
+            trace_count: total_trace_count,
+        });
+    }
+
+    build_variants
+}
+
+// `CMAKE_CONFIGURATION_TYPES` of the `CMakeCache.txt` next to the `CMakeFiles` directory, set by multi config generators
+fn configuration_types(cmake_file_dir_path: &Path) -> Option<Vec<String>> {
+    let cache_path = cmake_file_dir_path.parent()?.join("CMakeCache.txt");
+    let content = std::fs::read_to_string(cache_path).ok()?;
+
+    let (_, value) = content
+        .lines()
+        .find(|line| line.starts_with("CMAKE_CONFIGURATION_TYPES:"))?
+        .split_once('=')?;
+
+    Some(value.split(';').filter(|name| !name.is_empty()).map(|name| name.to_string()).collect())
+}
+
 // For a single config, iterate recursively over the `build_path` and add all the `.json` files
 //  - Remove all `CMakeFiles` directories from the final relative path
 //  - Remove all `.dir` directories from the final relative path
@@ -123,8 +279,8 @@ fn enumerate_all_trace_file_for_single_config(build_path: &str) -> Result<Vec<Pr
             continue;
         }
 
-        // Skip the `compile_commands.json` file
-        if s.ends_with("compile_commands.json") {
+        // Skip the `compile_commands.json` file and the exported report
+        if s.ends_with("compile_commands.json") || path.file_name().is_some_and(|n| n == report::REPORT_FILE_NAME) {
             continue;
         }
 
@@ -163,7 +319,16 @@ fn enumerate_all_trace_file_for_single_config(build_path: &str) -> Result<Vec<Pr
 }
 
 
-pub fn analyze_path(selected_path: &str, build_variant: BuildVariant) -> Result<AnalyisisResult, AnalysisError> {
+// Analyze the build directory, reports the progress through `on_progress` (called from worker threads)
+// and stops early with `AnalysisError::Cancelled` once `cancel` is set.
+// Trace files found in `file_cache` with the same size and modification time are not parsed again.
+pub fn analyze_path(
+    selected_path: &str,
+    build_variant: &BuildVariant,
+    file_cache: FileCache,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(AnalysisProgress) + Sync),
+) -> Result<AnalyisisResult, AnalysisError> {
     let resolved_cmake_files_path = match build_path_inference::infer_cmake_cache_dir_path_from_source_dir_path(selected_path) {
         Ok(path) => path,
         Err(e) => return Err(AnalysisError::InvalidPath(e)),
@@ -171,89 +336,230 @@ pub fn analyze_path(selected_path: &str, build_variant: BuildVariant) -> Result<
 
     let mut summary: Summary = Default::default();
 
-    let mut demangle_cache = BTreeMap::new();
-
     let trace_files = match build_variant {
         BuildVariant::SingleConfig => enumerate_all_trace_file_for_single_config(selected_path),
-        multi_config_value => enumerate_all_trace_files_for_multi_config(&resolved_cmake_files_path, &multi_config_value.to_string()),
-    };
+        BuildVariant::Config(name) => enumerate_all_trace_files_for_multi_config(&resolved_cmake_files_path, name),
+    }?;
+
+    let files_enumerated = trace_files.len();
+
+    on_progress(AnalysisProgress {
+        files_enumerated,
+        ..Default::default()
+    });
+
+    let files_parsed = AtomicUsize::new(0);
+    let files_reused = AtomicUsize::new(0);
+    let invalid_files = AtomicUsize::new(0);
+
+    // Read and parse all files in parallel, each worker keeps its own demangle cache.
+    // `collect` keeps the enumeration order, so merging is deterministic.
+    // Every parsed file is merged, only the ones with metadata are kept in the cache
+    let file_summaries: Vec<_> = trace_files
+        .par_iter()
+        .map_init(BTreeMap::new, |demangle_cache, trace_file_info| {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+
+            // Files without metadata are always parsed and never cached
+            let metadata = std::fs::metadata(&trace_file_info.path)
+                .ok()
+                .and_then(|m| Some((m.len(), m.modified().ok()?)));
+
+            let cached = metadata.and_then(|(size, modified)| file_cache.get(&trace_file_info.path, size, modified));
+
+            let file_summary = match cached {
+                Some(cached) => {
+                    files_reused.fetch_add(1, Ordering::Relaxed);
+                    cached.clone()
+                }
+                None => {
+                    let file_summary = process_single_file(
+                        demangle_cache,
+                        &trace_file_info.target_name,
+                        &trace_file_info.path,
+                        &trace_file_info.relative_path
+                    ).map(Arc::new);
+
+                    files_parsed.fetch_add(1, Ordering::Relaxed);
+                    file_summary
+                }
+            };
+
+            // Counted like `Summary::total_invalid_files`, which does not include unreadable files
+            let is_invalid = file_summary.as_ref().is_some_and(|f| !f.is_valid);
+
+            let invalid_files = if is_invalid {
+                invalid_files.fetch_add(1, Ordering::Relaxed) + 1
+            } else {
+                invalid_files.load(Ordering::Relaxed)
+            };
+
+            on_progress(AnalysisProgress {
+                files_enumerated,
+                files_parsed: files_parsed.load(Ordering::Relaxed),
+                files_reused: files_reused.load(Ordering::Relaxed),
+                invalid_files,
+            });
+
+            let cache_entry = metadata.map(|(size, modified)| {
+                (trace_file_info.path.clone(), CachedFileSummary { size, modified, summary: file_summary.clone() })
+            });
+
+            Some((file_summary, cache_entry))
+        })
+        .collect();
+
+    if cancel.load(Ordering::Relaxed) {
+        return Err(AnalysisError::Cancelled);
+    }
+
+    // Files which are gone are dropped from the cache here, as only the enumerated ones are kept
+    let mut new_file_cache = FileCache::default();
 
-    for trace_file_info in trace_files? {
-        process_single_file(
-            &mut summary,
-            &mut demangle_cache,
-            &trace_file_info.target_name,
-            &trace_file_info.path,
-            &trace_file_info.relative_path
-        );
+    for (file_summary, cache_entry) in file_summaries.into_iter().flatten() {
+        if let Some(file_summary) = &file_summary {
+            file_summary::merge_file_summary(&mut summary, file_summary);
+        }
+        if let Some((path, cached)) = cache_entry {
+            new_file_cache.files.insert(path, cached);
+        }
     }
 
+    trace!("Reused {} of {} trace files", files_reused.load(Ordering::Relaxed), files_enumerated);
+
     trace!("Analyzing path: {}", resolved_cmake_files_path);
 
-    process_summary_indices(&mut summary);
+    summary.target_dependencies = target_graph::read_target_dependencies(&resolved_cmake_files_path);
+
+    // Canonical, so that the absolute paths of `compile_commands.json` and the ninja log join them
+    // whatever path was selected
+    let trace_sources: BTreeMap<PathBuf, String> = trace_files
+        .iter()
+        .map(|trace_file_info| (canonical_path(&trace_file_info.path), trace_file_info.relative_path.clone()))
+        .collect();
 
-    process_include_indices(&mut summary);
+    let build_path = Path::new(&resolved_cmake_files_path).parent().unwrap_or(Path::new(selected_path));
 
-    process_source_indices(&mut summary);
+    summary.ninja_steps = ninja_log::read_ninja_log(build_path, build_variant, &trace_sources).unwrap_or_else(|e| {
+        error!("{}", e);
+        Vec::new()
+    });
 
-    process_frontend_indices(&mut summary);
+    summary.compile_commands = compile_commands::read_compile_commands(build_path, &trace_sources).unwrap_or_else(|e| {
+        error!("{}", e);
+        BTreeMap::new()
+    });
 
-    process_backend_indices(&mut summary);
+    process_all_indices(&mut summary);
 
     Ok(AnalyisisResult {
         selected_path: selected_path.to_string(),
         resolved_cmake_files_path,
-        build_variant,
+        build_variant: build_variant.clone(),
+        report_path: None,
         summary,
+        file_cache: new_file_cache,
     })
 }
 
+// Open either a build directory or a JSON report previously written by `report::write_report`
+pub fn load_path(selected_path: &str, build_variant: &BuildVariant) -> Result<AnalyisisResult, AnalysisError> {
+    load_path_with_progress(selected_path, build_variant, FileCache::default(), &AtomicBool::new(false), &|_| {})
+}
+
+pub fn load_path_with_progress(
+    selected_path: &str,
+    build_variant: &BuildVariant,
+    file_cache: FileCache,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(AnalysisProgress) + Sync),
+) -> Result<AnalyisisResult, AnalysisError> {
+    if Path::new(selected_path).is_file() {
+        report::load_report(selected_path)
+    } else {
+        analyze_path(selected_path, build_variant, file_cache, cancel, on_progress)
+    }
+}
+
+// Resolve symlinks and relative parts, the path is kept as is when it does not exist
+fn canonical_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+// Recompute all sorted index vectors from the summary maps
+fn process_all_indices(summary: &mut Summary) {
+    process_summary_indices(summary);
+
+    process_include_indices(summary);
+
+    process_source_indices(summary);
+
+    summary.flag_groups = compile_commands::compute_flag_groups(summary);
+
+    process_frontend_indices(summary);
+
+    process_template_group_indices(summary);
+
+    process_backend_indices(summary);
+
+    summary.namespace_tree = namespace_tree::compute_namespace_tree(summary);
+
+    process_event_indices(summary);
+
+    process_pass_indices(summary);
+
+    summary.compile_jobs.sort_by(|a, b| (a.start_time, &a.source).cmp(&(b.start_time, &b.source)));
+    summary.timeline = timeline::compute_timeline(summary);
+    summary.parallelism = timeline::compute_parallelism(summary, None);
+
+    summary.critical_path = target_graph::compute_critical_path(summary);
+
+    summary.ninja = ninja_log::compute_ninja_summary(summary);
+}
+
+// Read and parse a single trace file. Returns `None` when the file cannot be read at all.
 fn process_single_file(
-    summary: &mut Summary,
     demangle_cache: &mut BTreeMap<String, String>,
     target_name: &str,
     path: &Path,
     readable_path: &str
-) {
+) -> Option<FileSummary> {
     let Ok(content) = std::fs::read_to_string(path) else {
         error!("Failed to read file: {:?}", path);
-        return;
+        return None;
     };
 
-    let target_summary = summary
-        .target_summaries
-        .entry(target_name.to_string())
-        .or_default();
+    let mut file_summary = FileSummary {
+        target_name: target_name.to_string(),
+        readable_path: readable_path.to_string(),
+        trace_path: path.to_path_buf(),
+        ..Default::default()
+    };
 
     let Ok(parsed) = serde_json::from_str::<trace_format::Profile>(&content) else {
         error!("Failed to parse JSON: {:?}", path);
-        summary.total_invalid_files += 1;
-        return;
+        return Some(file_summary);
     };
 
-    summary.total_valid_files += 1;
-    target_summary.total_files += 1;
-
-    if summary.first_event_time == 0 {
-        summary.first_event_time = parsed.beginningOfTime;
-    } else {
-        summary.first_event_time = summary.first_event_time.min(parsed.beginningOfTime);
-    }
-
-    let mut frontend_duration_total_us = 0;
-    let mut backend_duration_total_us = 0;
+    file_summary.is_valid = true;
+    file_summary.beginning_of_time = parsed.beginningOfTime;
 
     let mut sources = Vec::new();
     let mut frontend_events = Vec::new();
     let mut backend_events = Vec::new();
+    let mut phases = Vec::new();
 
     for item in parsed.traceEvents.iter() {
         match item.name {
             "Frontend" => {
-                frontend_duration_total_us += item.dur.unwrap_or_default();
+                file_summary.frontend_duration_us += item.dur.unwrap_or_default();
+                phases.push(item);
             },
             "Backend" => {
-                backend_duration_total_us += item.dur.unwrap_or_default();
+                file_summary.backend_duration_us += item.dur.unwrap_or_default();
+                phases.push(item);
             },
             "Source" => {
                 sources.push(item);
@@ -268,40 +574,226 @@ fn process_single_file(
         }
     }
 
-    let my_end_of_time = parsed.beginningOfTime + frontend_duration_total_us as u128 + backend_duration_total_us as u128;
+    let recorded_events: Vec<&trace_format::Event> = sources
+        .iter()
+        .chain(frontend_events.iter())
+        .chain(backend_events.iter())
+        .copied()
+        .collect();
 
-    summary.last_event_time = summary.last_event_time.max(my_end_of_time);
+    process_uncovered_time(&mut file_summary, &phases, &recorded_events);
 
-    summary.frontend_duration_total_us += frontend_duration_total_us;
-    summary.backend_duration_total_us += backend_duration_total_us;
+    process_include_tree(&mut file_summary, &sources);
 
-    target_summary.total_frontend_duration_us += frontend_duration_total_us;
-    target_summary.total_backend_duration_us += backend_duration_total_us;
+    process_sources(&mut file_summary, sources);
 
-    if target_summary.first_event_time == 0 {
-        target_summary.first_event_time = parsed.beginningOfTime;
-    } else {
-        target_summary.first_event_time = target_summary.first_event_time.min(parsed.beginningOfTime);
+    process_frontend_operations(&mut file_summary, frontend_events);
+
+    process_backend_operations(&mut file_summary, demangle_cache, backend_events);
+
+    process_events(&mut file_summary, &parsed.traceEvents);
+
+    process_passes(&mut file_summary, &parsed.traceEvents);
+
+    Some(file_summary)
+}
+
+// Aggregate every event by its name and detail. Instant and metadata events have no duration and are skipped.
+fn process_events(file_summary: &mut FileSummary, events: &[trace_format::Event]) {
+    let mut events: Vec<&trace_format::Event> = events.iter().filter(|e| e.dur.is_some()).collect();
+
+    let self_times = nested_self_times(&mut events);
+
+    for (event, self_time_us) in events.iter().zip(self_times) {
+        let detail = event.args.as_ref().and_then(|a| a.detail.clone()).unwrap_or_default();
+
+        let event_summary = file_summary.events.entry((event.name.to_string(), detail)).or_default();
+
+        event_summary.num += 1;
+        event_summary.total_time_us += event.dur.unwrap_or_default();
+        event_summary.self_time_us += self_time_us;
     }
+}
+
+// Name of the LLVM pass an event stands for. The legacy pass manager records every pass as `RunPass`
+// with the pass name as detail, the new one records the pass itself, e.g. `InstCombinePass` with the
+// function as detail. The pipelines around them like `OptModule` or `CodeGenPasses` count as passes
+// too, so that their self time is the time not spent in any recorded pass.
+fn pass_name(event: &trace_format::Event) -> Option<String> {
+    match event.name {
+        "RunPass" | "RunLoopPass" => event.args.as_ref().and_then(|a| a.detail.clone()),
+        "OptModule" | "OptFunction" | "CodeGenPasses" | "PerFunctionPasses" | "PerModulePasses" | "Optimizer" | "CodeGen" => {
+            Some(event.name.to_string())
+        }
+        name if name.ends_with("Pass") || name.starts_with("PassManager") => Some(name.to_string()),
+        _ => None,
+    }
+}
 
-    target_summary.last_event_time = target_summary.last_event_time.max(my_end_of_time);
+// Aggregate the LLVM passes by their name, the self time only excludes the passes nested in them
+fn process_passes(file_summary: &mut FileSummary, events: &[trace_format::Event]) {
+    let mut passes: Vec<(&trace_format::Event, String)> = events
+        .iter()
+        .filter(|e| e.dur.is_some())
+        // `Total <pass>` events sum every run of a pass, they are not a pass of their own
+        .filter(|e| !e.name.starts_with("Total "))
+        .filter_map(|e| Some((e, pass_name(e)?)))
+        .collect();
 
-    process_sources(summary, sources);
+    // Already in the order of the nesting, so that the self times line up with the passes
+    passes.sort_by_key(|(e, _)| nesting_order(e));
 
-    let source_file_summary = summary.source_file_process_summaries
-        .entry(readable_path.to_string())
-        .or_default();
+    let mut events: Vec<&trace_format::Event> = passes.iter().map(|(e, _)| *e).collect();
+    let self_times = nested_self_times(&mut events);
 
-    source_file_summary.total_time_us = frontend_duration_total_us + backend_duration_total_us;
-    source_file_summary.total_frontend_time_us = frontend_duration_total_us;
-    source_file_summary.total_backend_time_us = backend_duration_total_us;
+    for ((event, name), self_time_us) in passes.into_iter().zip(self_times) {
+        let pass_summary = file_summary.passes.entry(name).or_default();
 
-    process_frontend_operations(summary, frontend_events);
+        pass_summary.num += 1;
+        pass_summary.total_time_us += event.dur.unwrap_or_default();
+        pass_summary.self_time_us += self_time_us;
+    }
+}
 
-    process_backend_operations(summary, demangle_cache, backend_events);
+// On equal timestamps the longer event is the parent
+fn nesting_order(event: &trace_format::Event) -> (u64, u64, u64, std::cmp::Reverse<Option<u64>>) {
+    (event.pid, event.tid, event.ts, std::cmp::Reverse(event.dur))
 }
 
-fn process_sources(summary: &mut Summary, mut sources: Vec<&trace_format::Event>) {
+// Sort the events by thread and start time, and return the self time of every one of them.
+// Events only nest on the same thread, so the self time of an event is its duration minus the
+// duration of the events directly nested in it on its thread.
+fn nested_self_times(events: &mut [&trace_format::Event]) -> Vec<u64> {
+    events.sort_by_key(|e| nesting_order(e));
+
+    let mut self_times: Vec<u64> = events.iter().map(|e| e.dur.unwrap_or_default()).collect();
+
+    // Indices and end times of the currently open events of the current thread
+    let mut stack: Vec<(usize, u64)> = Vec::new();
+
+    for (index, event) in events.iter().enumerate() {
+        let is_new_thread = index > 0 && (events[index - 1].pid, events[index - 1].tid) != (event.pid, event.tid);
+
+        if is_new_thread {
+            stack.clear();
+        }
+
+        // The `Total *` events sum up all events of a name and start at 0, they are not nested in anything
+        if event.name.starts_with("Total ") {
+            continue;
+        }
+
+        let duration = event.dur.unwrap_or_default();
+        let end_time = event.ts + duration;
+
+        // Close the events which ended before this one
+        while stack.last().is_some_and(|(_, parent_end_time)| *parent_end_time < end_time) {
+            stack.pop();
+        }
+
+        if let Some((parent_index, _)) = stack.last() {
+            self_times[*parent_index] = self_times[*parent_index].saturating_sub(duration);
+        }
+
+        stack.push((index, end_time));
+    }
+
+    self_times
+}
+
+// Merge the time ranges of the events into sorted, non-overlapping ranges
+fn merged_time_ranges(events: &[&trace_format::Event]) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = events
+        .iter()
+        .filter_map(|e| Some((e.ts, e.ts + e.dur?)))
+        .collect();
+
+    ranges.sort();
+
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+// Time of the frontend and backend phases during which none of the events which are summarized
+// (sources, frontend and backend operations) was running
+fn process_uncovered_time(file_summary: &mut FileSummary, phases: &[&trace_format::Event], recorded_events: &[&trace_format::Event]) {
+    let phases = merged_time_ranges(phases);
+    let recorded = merged_time_ranges(recorded_events);
+
+    let phases_duration: u64 = phases.iter().map(|(start, end)| end - start).sum();
+
+    // Both lists are sorted and non-overlapping, so walk them side by side and sum the intersections
+    let mut covered = 0;
+    let mut recorded_iter = recorded.iter().peekable();
+
+    for (phase_start, phase_end) in phases.iter() {
+        while let Some((start, end)) = recorded_iter.peek() {
+            covered += end.min(phase_end).saturating_sub(*start.max(phase_start));
+
+            // Keep the range if it continues into the next phase
+            if end > phase_end {
+                break;
+            }
+            recorded_iter.next();
+        }
+    }
+
+    file_summary.uncovered_duration_us = phases_duration - covered;
+}
+
+// Build the include hierarchy of the file. A source is a child of the innermost source which
+// is still open when it starts, and that chain of parents is its path in the tree.
+fn process_include_tree(file_summary: &mut FileSummary, sources: &[&trace_format::Event]) {
+    let mut sources = sources.to_vec();
+
+    // On equal timestamps the longer source is the parent
+    sources.sort_by_key(|s| (s.ts, std::cmp::Reverse(s.dur.unwrap_or_default())));
+
+    let root = &mut file_summary.include_tree;
+    root.num = 1;
+    root.total_time_us = file_summary.frontend_duration_us;
+
+    // Names and end times of the currently open sources
+    let mut stack: Vec<(&str, u64)> = Vec::new();
+
+    for source in sources.iter() {
+        let Some(name) = source.args.as_ref().and_then(|a| a.detail.as_deref()) else {
+            continue;
+        };
+
+        let time_spent = source.dur.unwrap_or_default();
+        let self_end_time = source.ts + time_spent;
+
+        // Close the sources which ended before this one
+        while stack.last().is_some_and(|(_, parent_end_time)| *parent_end_time < self_end_time) {
+            stack.pop();
+        }
+
+        let mut node = &mut *root;
+
+        for (parent_name, _) in stack.iter() {
+            node = node.children.get_mut(*parent_name).unwrap();
+        }
+
+        let node = node.children.entry(name.to_string()).or_default();
+        node.num += 1;
+        node.total_time_us += time_spent;
+
+        stack.push((name, self_end_time));
+    }
+
+    root.update_self_times();
+}
+
+fn process_sources(file_summary: &mut FileSummary, mut sources: Vec<&trace_format::Event>) {
     // Sort the sources by the timestamp to ensure that the parent will be always processed
     // before the child
     sources.sort_by_key(|s| s.ts);
@@ -320,8 +812,8 @@ fn process_sources(summary: &mut Summary, mut sources: Vec<&trace_format::Event>
             let self_end_time = source.ts + time_spent;
 
             // Add time to the self
-            let source_summary = summary
-                .frontend_file_process_summaries
+            let source_summary = file_summary
+                .includes
                 .entry(name.to_string())
                 .or_default();
 
@@ -337,12 +829,12 @@ fn process_sources(summary: &mut Summary, mut sources: Vec<&trace_format::Event>
 
                 // If the parent end time is greater than the current source end time
                 if parent_end_time > self_end_time {
-                    let parent_summary = summary
-                        .frontend_file_process_summaries
+                    let parent_summary = file_summary
+                        .includes
                         .get_mut(&top_parent.0)
                         .unwrap();
 
-                    parent_summary.self_time_us -= time_spent;
+                    parent_summary.self_time_us = parent_summary.self_time_us.saturating_sub(time_spent);
                 }
             }
 
@@ -425,6 +917,11 @@ fn process_include_indices(summary: &mut Summary) {
 
         b_time.cmp(&a_time)
     });
+
+    // Most expensive translation unit first for every include
+    for include_summary in summary.frontend_file_process_summaries.values_mut() {
+        include_summary.translation_units.sort_by_key(|t| std::cmp::Reverse(t.total_time_us));
+    }
 }
 
 fn process_source_indices(summary: &mut Summary) {
@@ -461,7 +958,7 @@ fn process_source_indices(summary: &mut Summary) {
     });
 }
 
-fn process_frontend_operations(summary: &mut Summary, mut frontend_events: Vec<&trace_format::Event>) {
+fn process_frontend_operations(file_summary: &mut FileSummary, mut frontend_events: Vec<&trace_format::Event>) {
     // Sort the sources by the timestamp to ensure that the parent will be always processed
     // before the child
     frontend_events.sort_by_key(|s| s.ts);
@@ -491,8 +988,8 @@ fn process_frontend_operations(summary: &mut Summary, mut frontend_events: Vec<&
 
         let self_end_time = item.ts + time_spent;
 
-        let source_summary = summary
-            .frontend_operation_summaries
+        let source_summary = file_summary
+            .frontend_operations
             .entry(key.clone())
             .or_default();
 
@@ -509,12 +1006,12 @@ fn process_frontend_operations(summary: &mut Summary, mut frontend_events: Vec<&
 
             // If the parent end time is greater than the current source end time
             if parent_end_time > self_end_time {
-                let parent_summary = summary
-                    .frontend_operation_summaries
+                let parent_summary = file_summary
+                    .frontend_operations
                     .get_mut(&top_parent.0)
                     .unwrap();
 
-                parent_summary.self_time_us -= time_spent;
+                parent_summary.self_time_us = parent_summary.self_time_us.saturating_sub(time_spent);
             }
         }
 
@@ -563,8 +1060,30 @@ fn process_frontend_indices(summary: &mut Summary) {
     });
 }
 
+// Demangle the detail of a backend event, using the cache for names which were seen before
+fn demangle_backend_name(demangle_cache: &mut BTreeMap<String, String>, op_name: &str) -> String {
+    // At this point the name is still surrounded with parenthesis. Remove them.
+    let op_name = op_name.trim_start_matches('(').trim_end_matches(')').to_string();
+
+    let demangle_options = DemangleOptions::default();
+
+    // Demangle the name, check the cache first
+    match demangle_cache.get(&op_name) {
+        Some(d) => d.clone(),
+        None => {
+            let demangled = Symbol::new(&op_name)
+                .ok() // Just convert to an option... do not care about the error
+                .and_then(|s| s.demangle(&demangle_options).ok()) // Demangle and convert to an option to handle errors
+                .unwrap_or(op_name.clone()); // Just return the mangled name if demangling fails
+
+            demangle_cache.insert(op_name.clone(), demangled.clone());
+            demangled
+        }
+    }
+}
+
 fn process_backend_operations(
-    summary: &mut Summary,
+    file_summary: &mut FileSummary,
     demangle_cache: &mut BTreeMap<String, String>,
     backend_events: Vec<&trace_format::Event>
 ) {
@@ -578,36 +1097,19 @@ fn process_backend_operations(
             .unwrap()
             .to_string();
 
-        // At this point the name is still surrounded with parenthesis. Remove them.
-        let op_name = op_name.trim_start_matches('(').trim_end_matches(')').to_string();
-
-        let demangle_options = DemangleOptions::default();
-
-        // Demangle the name, check the cache first
-        let demangled = match demangle_cache.get(&op_name) {
-            Some(d) => d.clone(),
-            None => {
-                let demangled = Symbol::new(&op_name)
-                    .ok() // Just convert to an option... do not care about the error
-                    .and_then(|s| s.demangle(&demangle_options).ok()) // Demangle and convert to an option to handle errors
-                    .unwrap_or(op_name.clone()); // Just return the mangled name if demangling fails
-
-                demangle_cache.insert(op_name.clone(), demangled.clone());
-                demangled
-            }
-        };
+        let demangled = demangle_backend_name(demangle_cache, &op_name);
 
         let time_spent = item.dur.unwrap_or_default();
 
-        let source_summary = summary
-            .backend_operation_summaries
+        let source_summary = file_summary
+            .backend_operations
             .entry(demangled)
             .or_default();
 
         source_summary.num += 1;
         source_summary.total_time_us += time_spent;
 
-        summary.backend_duration_single_events_us += time_spent;
+        file_summary.backend_duration_single_events_us += time_spent;
     }
 }
 
@@ -627,3 +1129,169 @@ fn process_backend_indices(summary: &mut Summary) {
         b_time.cmp(&a_time)
     });
 }
+
+fn process_event_indices(summary: &mut Summary) {
+    summary.event_name_summaries = BTreeMap::new();
+
+    for ((name, _), event_summary) in summary.event_summaries.iter() {
+        summary.event_name_summaries.entry(name.clone()).or_default().add(event_summary);
+    }
+
+    summary.event_largest_total_time_indices = summary.event_summaries.keys().cloned().collect();
+    summary.event_largest_self_time_indices = summary.event_largest_total_time_indices.clone();
+
+    // Largest first
+    summary.event_largest_total_time_indices.sort_by_key(|key| std::cmp::Reverse(summary.event_summaries[key].total_time_us));
+    summary.event_largest_self_time_indices.sort_by_key(|key| std::cmp::Reverse(summary.event_summaries[key].self_time_us));
+
+    summary.event_name_largest_total_time_indices = summary.event_name_summaries.keys().cloned().collect();
+    summary.event_name_largest_self_time_indices = summary.event_name_largest_total_time_indices.clone();
+
+    summary.event_name_largest_total_time_indices.sort_by_key(|name| std::cmp::Reverse(summary.event_name_summaries[name].total_time_us));
+    summary.event_name_largest_self_time_indices.sort_by_key(|name| std::cmp::Reverse(summary.event_name_summaries[name].self_time_us));
+}
+
+fn process_pass_indices(summary: &mut Summary) {
+    summary.pass_largest_total_time_indices = summary.pass_summaries.keys().cloned().collect();
+    summary.pass_largest_self_time_indices = summary.pass_largest_total_time_indices.clone();
+
+    // Largest first
+    summary.pass_largest_total_time_indices.sort_by_key(|name| std::cmp::Reverse(summary.pass_summaries[name].total_time_us));
+    summary.pass_largest_self_time_indices.sort_by_key(|name| std::cmp::Reverse(summary.pass_summaries[name].self_time_us));
+}
+
+fn process_template_group_indices(summary: &mut Summary) {
+    summary.template_groups = template_groups::compute_template_groups(summary);
+
+    summary.template_group_largest_total_time_indices = summary.template_groups.keys().cloned().collect();
+    summary.template_group_largest_self_time_indices = summary.template_group_largest_total_time_indices.clone();
+
+    // Largest first
+    summary.template_group_largest_total_time_indices.sort_by_key(|key| std::cmp::Reverse(summary.template_groups[key].total_time_us));
+    summary.template_group_largest_self_time_indices.sort_by_key(|key| std::cmp::Reverse(summary.template_groups[key].self_time_us));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicBool;
+
+    use super::*;
+
+    // Single config build directory in the temporary directory, removed when dropped
+    struct Fixture(PathBuf);
+
+    impl Fixture {
+        // `targets` targets with `files` trace files each, sharing headers and templates between them
+        fn new(name: &str, targets: usize, files: usize) -> Self {
+            let root = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+
+            for target in 0..targets {
+                let dir = root.join("CMakeFiles").join(format!("t{}.dir", target)).join("src");
+                std::fs::create_dir_all(&dir).unwrap();
+
+                for file in 0..files {
+                    let seed = (target * files + file) as u64;
+                    std::fs::write(dir.join(format!("f{}.cpp.json", file)), trace(seed)).unwrap();
+                }
+            }
+
+            // An invalid trace file is counted, but contributes nothing
+            std::fs::write(root.join("CMakeFiles/t0.dir/src/broken.cpp.json"), "{").unwrap();
+
+            Fixture(root)
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn event(name: &str, ts: u64, dur: u64, detail: Option<&str>) -> String {
+        match detail {
+            Some(detail) => format!(r#"{{"name": "{}", "ts": {}, "dur": {}, "args": {{"detail": "{}"}}, "pid": 1, "tid": 1, "ph": "X"}}"#, name, ts, dur, detail),
+            None => format!(r#"{{"name": "{}", "ts": {}, "dur": {}, "pid": 1, "tid": 1, "ph": "X"}}"#, name, ts, dur),
+        }
+    }
+
+    fn trace(seed: u64) -> String {
+        let header = |i: u64| format!("/usr/include/h{}.h", (seed + i) % 5);
+        let frontend = 1000 + seed * 37 % 500;
+        let backend = 200 + seed * 13 % 100;
+
+        let events = [
+            event("Source", 0, 400 + seed % 7, Some(&header(0))),
+            event("Source", 10, 100 + seed % 3, Some(&header(1))),
+            event("Source", 500, 50, Some(&header(2))),
+            event("ParseClass", 600, 30 + seed % 11, Some("ns::Foo")),
+            event("InstantiateFunction", 700, 20 + seed % 5, Some(&format!("ns::bar<{}>", seed % 3))),
+            event("Frontend", 0, frontend, None),
+            event("Backend", frontend, backend, None),
+            event("OptFunction", frontend + 10, backend / 2, Some("_ZN2ns3bazEv")),
+            event("Total Frontend", 0, frontend, None),
+        ];
+
+        format!(r#"{{"traceEvents": [{}], "beginningOfTime": {}}}"#, events.join(", "), 1_000_000 + seed * 2_000)
+    }
+
+    fn analyze_with_threads(path: &str, threads: usize) -> String {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+
+        let analysis = pool
+            .install(|| analyze_path(path, &BuildVariant::SingleConfig, FileCache::default(), &AtomicBool::new(false), &|_| {}))
+            .unwrap();
+
+        // The report holds every merged table, so equal reports mean equal summaries
+        serde_json::to_string(&report::Report::from_analysis(&analysis)).unwrap()
+    }
+
+    #[test]
+    fn parallel_analysis_matches_serial_analysis() {
+        let fixture = Fixture::new("parallel-analysis", 3, 20);
+
+        let serial = analyze_with_threads(fixture.path(), 1);
+        let parallel = analyze_with_threads(fixture.path(), 8);
+
+        assert!(serial.contains(r#""total_valid_files":60,"total_invalid_files":1"#), "{}", serial);
+        assert!(serial.contains("ns::Foo"));
+        assert_eq!(serial, parallel);
+    }
+
+    // Last progress reported by an analysis, the workers report out of order
+    fn analyze_with_progress(path: &str, file_cache: FileCache) -> (AnalyisisResult, AnalysisProgress) {
+        let progress = std::sync::Mutex::new(AnalysisProgress::default());
+
+        let analysis = analyze_path(path, &BuildVariant::SingleConfig, file_cache, &AtomicBool::new(false), &|p| {
+            let mut progress = progress.lock().unwrap();
+
+            if p.files_processed() >= progress.files_processed() {
+                *progress = p;
+            }
+        }).unwrap();
+
+        (analysis, progress.into_inner().unwrap())
+    }
+
+    #[test]
+    fn reused_files_are_not_counted_as_parsed() {
+        let fixture = Fixture::new("reused-files", 2, 3);
+
+        let (mut analysis, progress) = analyze_with_progress(fixture.path(), FileCache::default());
+
+        assert_eq!((progress.files_enumerated, progress.files_parsed, progress.files_reused), (7, 7, 0));
+
+        std::fs::write(fixture.0.join("CMakeFiles/t1.dir/src/f0.cpp.json"), trace(100) + " ").unwrap();
+
+        let (_, progress) = analyze_with_progress(fixture.path(), analysis.take_file_cache());
+
+        assert_eq!((progress.files_enumerated, progress.files_parsed, progress.files_reused), (7, 1, 6));
+        assert_eq!(progress.files_processed(), 7);
+        assert_eq!(progress.invalid_files, 1);
+    }
+}
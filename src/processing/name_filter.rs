@@ -0,0 +1,40 @@
+use regex::Regex;
+
+// Filter of the names listed in the browsing panes, by substring or by regular expression
+pub enum NameFilter {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl NameFilter {
+    // No filter for an empty text, the error message for an invalid regular expression
+    pub fn new(text: &str, regex: bool) -> Result<Option<NameFilter>, String> {
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        if regex {
+            Regex::new(text)
+                .map(|regex| Some(NameFilter::Regex(regex)))
+                .map_err(|e| e.to_string())
+        } else {
+            Ok(Some(NameFilter::Substring(text.to_string())))
+        }
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        match self {
+            NameFilter::Substring(substring) => name.contains(substring.as_str()),
+            NameFilter::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+// Keys of the sorted index vector `order` whose name matches the filter, all of them without a filter
+pub fn filtered<'a, K>(
+    order: &'a [K],
+    filter: Option<&'a NameFilter>,
+    name: impl Fn(&K) -> &str + 'a,
+) -> impl Iterator<Item = &'a K> + 'a {
+    order.iter().filter(move |key| filter.is_none_or(|filter| filter.is_match(name(key))))
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use super::summary::Summary;
+use super::template_groups::template_name;
+
+// Scope of symbols which are not inside any namespace or class
+pub const GLOBAL_SCOPE: &str = "(global)";
+
+// Node of the namespace hierarchy, the times include the ones of the children
+#[derive(Default, Clone)]
+pub struct NamespaceNode {
+    // Self time of the frontend operations, their total times overlap
+    pub frontend_time_us: u64,
+    pub backend_time_us: u64,
+    pub num: usize,
+    pub children: BTreeMap<String, NamespaceNode>,
+}
+
+impl NamespaceNode {
+    pub fn total_time_us(&self) -> u64 {
+        self.frontend_time_us + self.backend_time_us
+    }
+
+    // Children with the largest time first
+    pub fn sorted_children(&self, time_us: impl Fn(&NamespaceNode) -> u64) -> Vec<(&String, &NamespaceNode)> {
+        let mut children: Vec<_> = self.children.iter().collect();
+        children.sort_by_key(|(_, c)| std::cmp::Reverse(time_us(c)));
+        children
+    }
+
+    fn add(&mut self, scopes: &[String], frontend_time_us: u64, backend_time_us: u64, num: usize) {
+        self.frontend_time_us += frontend_time_us;
+        self.backend_time_us += backend_time_us;
+        self.num += num;
+
+        if let Some((first, rest)) = scopes.split_first() {
+            self.children
+                .entry(first.clone())
+                .or_default()
+                .add(rest, frontend_time_us, backend_time_us, num);
+        }
+    }
+}
+
+// Namespaces and classes enclosing the symbol, without template arguments.
+// `void ns::Foo<int>::bar(int)` -> `["ns", "Foo<>"]`, the symbol itself and its parameters are dropped.
+pub fn scopes(name: &str) -> Vec<String> {
+    let name = template_name(name);
+
+    let mut scopes = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut rest = name.as_str();
+
+    while let Some(c) = rest.chars().next() {
+        if depth == 0 {
+            if let Some(after) = rest.strip_prefix("::") {
+                scopes.push(std::mem::take(&mut current));
+                rest = after;
+                continue;
+            }
+
+            if c == '(' {
+                if current.ends_with("operator") {
+                    if let Some(after) = rest.strip_prefix("()") {
+                        current.push_str("()");
+                        rest = after;
+                        continue;
+                    }
+                }
+
+                // `(anonymous namespace)`, `(lambda at a.cpp:1:2)` or the parameters of a function enclosing a
+                // local class are followed by `::`, anything else starts the parameter list
+                match parenthesized_len(rest) {
+                    Some(len) if rest[len..].starts_with("::") => {
+                        current.push_str(&rest[..len]);
+                        rest = &rest[len..];
+                        continue;
+                    }
+                    _ => break,
+                }
+            }
+
+            // Anything before a space is the return type, except in `operator new`, `operator const char*` and the like.
+            // The pointer and reference of the return type stick to the name, `void *ns::foo()`.
+            if c == ' ' && !is_operator(&current) {
+                scopes.clear();
+                current.clear();
+                rest = rest[1..].trim_start_matches(['*', '&']);
+                continue;
+            }
+        }
+
+        match c {
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
+        current.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    scopes.retain(|scope| !scope.is_empty());
+    scopes
+}
+
+// Length of the leading parenthesized group of `s` including its parentheses, `None` if it is not closed
+fn parenthesized_len(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// `operator` followed by its operator or conversion type, but not an identifier like `operators`
+fn is_operator(name: &str) -> bool {
+    name.strip_prefix("operator").is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+// Sum the frontend operations and the backend symbols of the summary by their enclosing scopes
+pub fn compute_namespace_tree(summary: &Summary) -> NamespaceNode {
+    let mut root = NamespaceNode::default();
+
+    let scopes_or_global = |name: &str| {
+        let scopes = scopes(name);
+        if scopes.is_empty() { vec![GLOBAL_SCOPE.to_string()] } else { scopes }
+    };
+
+    for ((name, _), operation_summary) in summary.frontend_operation_summaries.iter() {
+        root.add(&scopes_or_global(name), operation_summary.self_time_us, 0, operation_summary.num);
+    }
+
+    for (name, operation_summary) in summary.backend_operation_summaries.iter() {
+        root.add(&scopes_or_global(name), 0, operation_summary.total_time_us, operation_summary.num);
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scopes;
+
+    #[test]
+    fn drops_symbol_and_template_arguments() {
+        assert_eq!(scopes("ns::inner::foo"), ["ns", "inner"]);
+        assert_eq!(scopes("ns::Foo<int>::bar(int)"), ["ns", "Foo<>"]);
+        assert_eq!(scopes("ns::Foo<ns::Bar<int>, (N > 1)>::bar"), ["ns", "Foo<>"]);
+        assert!(scopes("foo").is_empty());
+    }
+
+    #[test]
+    fn drops_return_type() {
+        assert_eq!(scopes("void ns::Foo<int>::bar(int)"), ["ns", "Foo<>"]);
+        assert_eq!(scopes("std::vector<int> ns::make()"), ["ns"]);
+        assert_eq!(scopes("unsigned long ns::Foo::size() const"), ["ns", "Foo"]);
+    }
+
+    #[test]
+    fn keeps_operators() {
+        assert_eq!(scopes("bool ns::operator<(const Foo &, const Foo &)"), ["ns"]);
+        assert_eq!(scopes("ns::operator<<(std::ostream &, const Foo &)"), ["ns"]);
+        assert_eq!(scopes("ns::Foo::operator()(int)"), ["ns", "Foo"]);
+        assert_eq!(scopes("void *ns::Foo::operator new(unsigned long)"), ["ns", "Foo"]);
+        assert_eq!(scopes("ns::Foo::operator const char *()"), ["ns", "Foo"]);
+        assert_eq!(scopes("const Foo &ns::Foo::operator=(const Foo &)"), ["ns", "Foo"]);
+    }
+
+    #[test]
+    fn keeps_parenthesized_scopes() {
+        assert_eq!(scopes("(anonymous namespace)::foo"), ["(anonymous namespace)"]);
+        assert_eq!(scopes("ns::(anonymous namespace)::Foo::bar()"), ["ns", "(anonymous namespace)", "Foo"]);
+        assert_eq!(scopes("ns::foo()::(lambda at a.cpp:1:2)::operator()()"), ["ns", "foo()", "(lambda at a.cpp:1:2)"]);
+        assert_eq!(scopes("ns::foo(int)::Local::bar"), ["ns", "foo(int)", "Local"]);
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+use walkdir::WalkDir;
+
+use crate::model::BuildVariant;
+
+use super::summary::Summary;
+use super::AnalysisError;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NinjaStepKind {
+    Compile,
+    Link,
+    Other,
+}
+
+impl NinjaStepKind {
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            NinjaStepKind::Compile => "CC",
+            NinjaStepKind::Link => "LD",
+            NinjaStepKind::Other => "OTH",
+        }
+    }
+}
+
+// A single build step of the last build recorded in `.ninja_log`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NinjaStep {
+    // Relative to the build directory, the first output when the step has several
+    pub output: String,
+    pub kind: NinjaStepKind,
+    pub target_name: Option<String>,
+    // Source of the trace file written next to the object file, for compile steps
+    pub source: Option<String>,
+    // Relative to the start of the build, ninja only records milliseconds
+    pub start_us: u64,
+    pub duration_us: u64,
+}
+
+// Read the steps of the last build from the `.ninja_log` of the build directory. Returns no steps
+// when the build directory was not built with ninja.
+//
+// `trace_sources` maps the paths of the trace files to their sources, compile steps are joined to
+// them by replacing the extension of the object file with `.json`.
+pub fn read_ninja_log(
+    build_path: &Path,
+    build_variant: &BuildVariant,
+    trace_sources: &BTreeMap<PathBuf, String>,
+) -> Result<Vec<NinjaStep>, AnalysisError> {
+    let log_path = build_path.join(".ninja_log");
+
+    if !log_path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&log_path)
+        .map_err(|e| AnalysisError::Other(format!("Failed to read {}: {}", log_path.display(), e)))?;
+
+    // All configurations of a multi config build share the log, their outputs are below a directory
+    // named after the configuration
+    let other_configurations: Vec<String> = match build_variant {
+        BuildVariant::Config(name) => super::configuration_types(&build_path.join("CMakeFiles"))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|configuration| configuration != name)
+            .collect(),
+        BuildVariant::SingleConfig => Vec::new(),
+    };
+
+    let steps = parse_ninja_log(&content, build_path, &cmake_target_names(build_path), &other_configurations, trace_sources);
+
+    trace!("Read {} steps from {}", steps.len(), log_path.display());
+
+    Ok(steps)
+}
+
+fn parse_ninja_log(
+    content: &str,
+    build_path: &Path,
+    target_names: &BTreeSet<String>,
+    other_configurations: &[String],
+    trace_sources: &BTreeMap<PathBuf, String>,
+) -> Vec<NinjaStep> {
+    // Each build appends its steps in the order they finished, so an end time lower than the previous one
+    // starts a new build. A step with several outputs is written once per output with the same times.
+    let mut entries: Vec<(u64, u64, &str, &str)> = Vec::new();
+    let mut last_end = 0;
+
+    for line in content.lines().filter(|line| !line.starts_with('#')) {
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        let [start, end, _mtime, output, hash] = fields.as_slice() else {
+            continue;
+        };
+
+        let (Ok(start), Ok(end)) = (start.parse::<u64>(), end.parse::<u64>()) else {
+            continue;
+        };
+
+        // Malformed line
+        if end < start {
+            continue;
+        }
+
+        if end < last_end {
+            entries.clear();
+        }
+
+        last_end = end;
+        entries.push((start, end, output, hash));
+    }
+
+    // Rebuilt outputs keep only their latest entry
+    let mut latest: BTreeMap<&str, (u64, u64, &str)> = BTreeMap::new();
+
+    for (start, end, output, hash) in entries {
+        if !output.split('/').any(|part| other_configurations.iter().any(|configuration| configuration == part)) {
+            latest.insert(output, (start, end, hash));
+        }
+    }
+
+    let mut seen_steps = BTreeSet::new();
+    let mut steps = Vec::new();
+
+    for (output, (start, end, hash)) in latest {
+        if !seen_steps.insert((start, end, hash)) {
+            continue;
+        }
+
+        let kind = step_kind(output, target_names);
+
+        let source = match kind {
+            NinjaStepKind::Compile => trace_sources
+                .get(&super::canonical_path(&build_path.join(Path::new(output).with_extension("json"))))
+                .cloned(),
+            _ => None,
+        };
+
+        let target_name = match kind {
+            NinjaStepKind::Link => linked_target_name(output),
+            _ => object_dir_target_name(output),
+        };
+
+        steps.push(NinjaStep {
+            output: output.to_string(),
+            kind,
+            target_name,
+            source,
+            start_us: start * 1000,
+            duration_us: (end - start) * 1000,
+        });
+    }
+
+    steps.sort_by_key(|step| (step.start_us, step.duration_us));
+
+    steps
+}
+
+// Names of the targets which have a `<target>.dir` in one of the `CMakeFiles` of the build directory
+fn cmake_target_names(build_path: &Path) -> BTreeSet<String> {
+    WalkDir::new(build_path)
+        .into_iter()
+        // The object files of a target are not needed
+        .filter_entry(|e| !e.file_name().to_string_lossy().ends_with(".dir"))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir() && e.file_name() == "CMakeFiles")
+        .filter_map(|e| std::fs::read_dir(e.path()).ok())
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str()?.strip_suffix(".dir").map(|name| name.to_string()))
+        .collect()
+}
+
+fn step_kind(output: &str, target_names: &BTreeSet<String>) -> NinjaStepKind {
+    let path = Path::new(output);
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    let is_shared_library = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.contains(".so.") || name.ends_with(".so"));
+
+    match extension {
+        "o" | "obj" => NinjaStepKind::Compile,
+        _ if is_shared_library => NinjaStepKind::Link,
+        "a" | "lib" | "dylib" | "dll" | "exe" => NinjaStepKind::Link,
+        // Executables have no extension on unix, unlike them stamp files and other generated
+        // outputs are not named after a target
+        "" if !output.contains("CMakeFiles") && linked_target_name(output).is_some_and(|name| target_names.contains(&name)) => NinjaStepKind::Link,
+        _ => NinjaStepKind::Other,
+    }
+}
+
+// `CMakeFiles/foo.dir/src/a.cpp.o` -> `foo`
+fn object_dir_target_name(output: &str) -> Option<String> {
+    output
+        .split('/')
+        .find_map(|part| part.strip_suffix(".dir"))
+        .map(|name| name.to_string())
+}
+
+// `lib/libfoo.so.1.2` or `bin/foo` -> `foo`
+fn linked_target_name(output: &str) -> Option<String> {
+    let file_name = Path::new(output).file_name()?.to_str()?;
+
+    let name = file_name.split('.').next().unwrap_or(file_name);
+
+    let name = match Path::new(output).extension().and_then(|e| e.to_str()) {
+        Some("lib" | "dll" | "exe") | None => name,
+        _ => name.strip_prefix("lib").unwrap_or(name),
+    };
+
+    Some(name.to_string())
+}
+
+// Time of the build steps of a target
+#[derive(Default, Clone)]
+pub struct NinjaTargetSteps {
+    pub compile_us: u64,
+    pub link_us: u64,
+    pub other_us: u64,
+}
+
+impl NinjaTargetSteps {
+    fn add(&mut self, kind: NinjaStepKind, duration_us: u64) {
+        match kind {
+            NinjaStepKind::Compile => self.compile_us += duration_us,
+            NinjaStepKind::Link => self.link_us += duration_us,
+            NinjaStepKind::Other => self.other_us += duration_us,
+        }
+    }
+
+    pub fn total_us(&self) -> u64 {
+        self.compile_us + self.link_us + self.other_us
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct NinjaSummary {
+    // Steps without a known target are kept under an empty name
+    pub target_steps: BTreeMap<String, NinjaTargetSteps>,
+    pub target_steps_largest_total_indices: Vec<String>,
+    pub totals: NinjaTargetSteps,
+    // From the start of the first step to the end of the last one
+    pub wall_time_us: u64,
+    // Part of the wall time during which no compile step was running
+    pub not_compiling_us: u64,
+    // Compile steps which were joined to a trace file
+    pub traced_compile_steps: usize,
+}
+
+pub fn compute_ninja_summary(summary: &Summary) -> NinjaSummary {
+    let mut ninja = NinjaSummary::default();
+
+    for step in summary.ninja_steps.iter() {
+        ninja.totals.add(step.kind, step.duration_us);
+
+        ninja.target_steps
+            .entry(step.target_name.clone().unwrap_or_default())
+            .or_default()
+            .add(step.kind, step.duration_us);
+
+        if step.source.is_some() {
+            ninja.traced_compile_steps += 1;
+        }
+    }
+
+    let first_start = summary.ninja_steps.iter().map(|step| step.start_us).min().unwrap_or_default();
+    let last_end = summary.ninja_steps.iter().map(|step| step.start_us + step.duration_us).max().unwrap_or_default();
+
+    ninja.wall_time_us = last_end - first_start;
+
+    // Steps are sorted by the start time, so the compile steps can be merged in a single pass
+    let mut compiling_us = 0;
+    let mut current: Option<(u64, u64)> = None;
+
+    for step in summary.ninja_steps.iter().filter(|step| step.kind == NinjaStepKind::Compile) {
+        let end = step.start_us + step.duration_us;
+
+        current = match current {
+            Some((start, current_end)) if step.start_us <= current_end => Some((start, current_end.max(end))),
+            Some((start, current_end)) => {
+                compiling_us += current_end - start;
+                Some((step.start_us, end))
+            }
+            None => Some((step.start_us, end)),
+        };
+    }
+
+    if let Some((start, end)) = current {
+        compiling_us += end - start;
+    }
+
+    ninja.not_compiling_us = ninja.wall_time_us - compiling_us;
+
+    let mut indices: Vec<String> = ninja.target_steps.keys().cloned().collect();
+    indices.sort_by_key(|name| std::cmp::Reverse(ninja.target_steps[name].total_us()));
+    ninja.target_steps_largest_total_indices = indices;
+
+    ninja
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str, other_configurations: &[&str], trace_sources: &[(&str, &str)]) -> Vec<NinjaStep> {
+        let target_names = ["app".to_string(), "core".to_string()].into_iter().collect();
+        let other_configurations: Vec<String> = other_configurations.iter().map(|c| c.to_string()).collect();
+        let trace_sources = trace_sources.iter().map(|(path, source)| (PathBuf::from(path), source.to_string())).collect();
+
+        parse_ninja_log(content, Path::new("/no/build"), &target_names, &other_configurations, &trace_sources)
+    }
+
+    fn outputs(steps: &[NinjaStep]) -> Vec<&str> {
+        steps.iter().map(|step| step.output.as_str()).collect()
+    }
+
+    #[test]
+    fn lower_end_time_starts_a_new_build() {
+        let log = "# ninja log v5\n\
+            0\t100\t0\tCMakeFiles/app.dir/a.cpp.o\th1\n\
+            0\t200\t0\tCMakeFiles/app.dir/b.cpp.o\th2\n\
+            5\t50\t0\tCMakeFiles/app.dir/a.cpp.o\th3\n";
+
+        let steps = parse(log, &[], &[]);
+
+        assert_eq!(outputs(&steps), ["CMakeFiles/app.dir/a.cpp.o"]);
+        assert_eq!(steps[0].start_us, 5000);
+        assert_eq!(steps[0].duration_us, 45000);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let log = "0\t100\t0\tCMakeFiles/app.dir/a.cpp.o\th1\n\
+            300\t200\t0\tCMakeFiles/app.dir/b.cpp.o\th2\n\
+            not a step\n\
+            x\t400\t0\tCMakeFiles/app.dir/c.cpp.o\th3\n";
+
+        assert_eq!(outputs(&parse(log, &[], &[])), ["CMakeFiles/app.dir/a.cpp.o"]);
+    }
+
+    #[test]
+    fn keeps_the_latest_entry_and_one_step_per_command() {
+        let log = "0\t100\t0\tCMakeFiles/app.dir/a.cpp.o\th1\n\
+            100\t300\t0\tgen/a.h\th2\n\
+            100\t300\t0\tgen/b.h\th2\n\
+            300\t400\t0\tCMakeFiles/app.dir/a.cpp.o\th3\n";
+
+        let steps = parse(log, &[], &[]);
+
+        assert_eq!(outputs(&steps), ["gen/a.h", "CMakeFiles/app.dir/a.cpp.o"]);
+        assert_eq!(steps[1].start_us, 300_000);
+    }
+
+    #[test]
+    fn classifies_and_joins_steps() {
+        let log = "0\t100\t0\tCMakeFiles/app.dir/src/a.cpp.o\th1\n\
+            0\t120\t0\tCMakeFiles/core.dir/src/b.cpp.o\th2\n\
+            120\t150\t0\tlib/libcore.a\th3\n\
+            150\t200\t0\tbin/app\th4\n\
+            200\t210\t0\tCMakeFiles/app.stamp\th5\n\
+            210\t220\t0\ttools/generator\th6\n";
+
+        let steps = parse(log, &[], &[("/no/build/CMakeFiles/app.dir/src/a.cpp.json", "src/a.cpp")]);
+
+        let kinds: Vec<_> = steps.iter().map(|step| (step.output.as_str(), step.kind, step.target_name.as_deref(), step.source.as_deref())).collect();
+
+        assert_eq!(kinds, [
+            ("CMakeFiles/app.dir/src/a.cpp.o", NinjaStepKind::Compile, Some("app"), Some("src/a.cpp")),
+            ("CMakeFiles/core.dir/src/b.cpp.o", NinjaStepKind::Compile, Some("core"), None),
+            ("lib/libcore.a", NinjaStepKind::Link, Some("core"), None),
+            ("bin/app", NinjaStepKind::Link, Some("app"), None),
+            ("CMakeFiles/app.stamp", NinjaStepKind::Other, None, None),
+            ("tools/generator", NinjaStepKind::Other, None, None),
+        ]);
+    }
+
+    #[test]
+    fn drops_the_steps_of_other_configurations() {
+        let log = "0\t100\t0\tCMakeFiles/app.dir/Debug/a.cpp.o\th1\n\
+            0\t120\t0\tCMakeFiles/app.dir/Release/a.cpp.o\th2\n\
+            120\t150\t0\tDebug/app\th3\n\
+            150\t200\t0\tRelease/app\th4\n\
+            200\t210\t0\tbuild.ninja\th5\n";
+
+        assert_eq!(outputs(&parse(log, &["Debug"], &[])), ["CMakeFiles/app.dir/Release/a.cpp.o", "Release/app", "build.ninja"]);
+    }
+}
@@ -0,0 +1,400 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::BuildVariant;
+
+use super::summary::{
+    BackendOperationSummaries,
+    CompileJob,
+    EventSummary,
+    FrontendFileProcessSummary,
+    FrontendOperation,
+    FrontendOperationSummary,
+    IncludeTreeNode,
+    SourceFileProcessSummary,
+    Summary,
+    TopLevelTargetSummary,
+};
+use super::compile_commands::CompileCommand;
+use super::ninja_log::NinjaStep;
+use super::target_graph::TargetDependencies;
+use super::timeline::Parallelism;
+use super::{AnalyisisResult, AnalysisError};
+
+// Bump this whenever the layout of the report changes in an incompatible way.
+// Reports with a newer version than this one are rejected when loading.
+pub const REPORT_FORMAT_VERSION: u32 = 1;
+
+// File name of the report exported into the analyzed build directory, it is not a trace file
+pub const REPORT_FILE_NAME: &str = "build-time-report.json";
+
+// Serialized form of an `AnalyisisResult`.
+//
+// The maps of the `Summary` are stored as lists of named entries so that the format does not depend on
+// the key types, and the sorted index vectors are not stored at all since they are recomputed on load.
+#[derive(Serialize, Deserialize)]
+pub struct Report {
+    pub format_version: u32,
+    pub selected_path: String,
+    pub resolved_cmake_files_path: String,
+    pub build_variant: BuildVariant,
+
+    pub totals: ReportTotals,
+
+    pub targets: Vec<NamedEntry<TopLevelTargetSummary>>,
+    pub includes: Vec<NamedEntry<FrontendFileProcessSummary>>,
+    pub include_tree: IncludeTreeNode,
+    pub sources: Vec<NamedEntry<SourceFileProcessSummary>>,
+    pub frontend_operations: Vec<FrontendOperationEntry>,
+    pub backend_operations: Vec<NamedEntry<BackendOperationSummaries>>,
+
+    pub compile_jobs: Vec<CompileJob>,
+    pub target_dependencies: TargetDependencies,
+    pub ninja_steps: Vec<NinjaStep>,
+    pub compile_commands: BTreeMap<String, CompileCommand>,
+    pub events: Vec<EventEntry>,
+    pub passes: Vec<NamedEntry<EventSummary>>,
+
+    // Derived from the compile jobs, only written for other tools and recomputed on load
+    #[serde(default, skip_deserializing)]
+    pub parallelism: Parallelism,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReportTotals {
+    pub total_valid_files: usize,
+    pub total_invalid_files: usize,
+    pub frontend_duration_total_us: u64,
+    pub backend_duration_total_us: u64,
+    pub backend_duration_single_events_us: u64,
+    pub first_event_time: u128,
+    pub last_event_time: u128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NamedEntry<T> {
+    pub name: String,
+    pub summary: T,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FrontendOperationEntry {
+    pub name: String,
+    pub operation: FrontendOperation,
+    pub summary: FrontendOperationSummary,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventEntry {
+    pub name: String,
+    pub detail: String,
+    pub summary: EventSummary,
+}
+
+impl Report {
+    pub fn from_analysis(analysis: &AnalyisisResult) -> Self {
+        let summary = &analysis.summary;
+
+        Report {
+            format_version: REPORT_FORMAT_VERSION,
+            selected_path: analysis.selected_path.clone(),
+            resolved_cmake_files_path: analysis.resolved_cmake_files_path.clone(),
+            build_variant: analysis.build_variant.clone(),
+            totals: ReportTotals {
+                total_valid_files: summary.total_valid_files,
+                total_invalid_files: summary.total_invalid_files,
+                frontend_duration_total_us: summary.frontend_duration_total_us,
+                backend_duration_total_us: summary.backend_duration_total_us,
+                backend_duration_single_events_us: summary.backend_duration_single_events_us,
+                first_event_time: summary.first_event_time,
+                last_event_time: summary.last_event_time,
+            },
+            targets: summary.target_summaries
+                .iter()
+                .map(|(name, s)| NamedEntry {
+                    name: name.clone(),
+                    summary: s.clone(),
+                })
+                .collect(),
+            includes: summary.frontend_file_process_summaries
+                .iter()
+                .map(|(name, s)| NamedEntry {
+                    name: name.clone(),
+                    summary: s.clone(),
+                })
+                .collect(),
+            include_tree: summary.include_tree.clone(),
+            sources: summary.source_file_process_summaries
+                .iter()
+                .map(|(name, s)| NamedEntry {
+                    name: name.clone(),
+                    summary: s.clone(),
+                })
+                .collect(),
+            frontend_operations: summary.frontend_operation_summaries
+                .iter()
+                .map(|((name, operation), s)| FrontendOperationEntry {
+                    name: name.clone(),
+                    operation: *operation,
+                    summary: s.clone(),
+                })
+                .collect(),
+            backend_operations: summary.backend_operation_summaries
+                .iter()
+                .map(|(name, s)| NamedEntry {
+                    name: name.clone(),
+                    summary: s.clone(),
+                })
+                .collect(),
+            compile_jobs: summary.compile_jobs.clone(),
+            target_dependencies: summary.target_dependencies.clone(),
+            ninja_steps: summary.ninja_steps.clone(),
+            compile_commands: summary.compile_commands.clone(),
+            events: summary.event_summaries
+                .iter()
+                .map(|((name, detail), s)| EventEntry {
+                    name: name.clone(),
+                    detail: detail.clone(),
+                    summary: s.clone(),
+                })
+                .collect(),
+            passes: summary.pass_summaries
+                .iter()
+                .map(|(name, s)| NamedEntry {
+                    name: name.clone(),
+                    summary: s.clone(),
+                })
+                .collect(),
+            parallelism: summary.parallelism.clone(),
+        }
+    }
+
+    pub fn into_analysis(self, report_path: &str) -> AnalyisisResult {
+        let mut summary = Summary {
+            total_valid_files: self.totals.total_valid_files,
+            total_invalid_files: self.totals.total_invalid_files,
+            frontend_duration_total_us: self.totals.frontend_duration_total_us,
+            backend_duration_total_us: self.totals.backend_duration_total_us,
+            backend_duration_single_events_us: self.totals.backend_duration_single_events_us,
+            first_event_time: self.totals.first_event_time,
+            last_event_time: self.totals.last_event_time,
+            ..Default::default()
+        };
+
+        summary.target_summaries = self.targets
+            .into_iter()
+            .map(|e| (e.name, e.summary))
+            .collect();
+
+        summary.frontend_file_process_summaries = self.includes
+            .into_iter()
+            .map(|e| (e.name, e.summary))
+            .collect();
+
+        summary.include_tree = self.include_tree;
+
+        summary.source_file_process_summaries = self.sources
+            .into_iter()
+            .map(|e| (e.name, e.summary))
+            .collect();
+
+        summary.frontend_operation_summaries = self.frontend_operations
+            .into_iter()
+            .map(|e| ((e.name, e.operation), e.summary))
+            .collect();
+
+        summary.backend_operation_summaries = self.backend_operations
+            .into_iter()
+            .map(|e| (e.name, e.summary))
+            .collect();
+
+        summary.compile_jobs = self.compile_jobs;
+        summary.target_dependencies = self.target_dependencies;
+        summary.ninja_steps = self.ninja_steps;
+        summary.compile_commands = self.compile_commands;
+
+        summary.event_summaries = self.events
+            .into_iter()
+            .map(|e| ((e.name, e.detail), e.summary))
+            .collect();
+
+        summary.pass_summaries = self.passes
+            .into_iter()
+            .map(|e| (e.name, e.summary))
+            .collect();
+
+        super::process_all_indices(&mut summary);
+
+        AnalyisisResult {
+            selected_path: self.selected_path,
+            resolved_cmake_files_path: self.resolved_cmake_files_path,
+            build_variant: self.build_variant,
+            report_path: Some(report_path.to_string()),
+            summary,
+            file_cache: Default::default(),
+        }
+    }
+}
+
+pub fn write_report(analysis: &AnalyisisResult, path: &str) -> Result<(), AnalysisError> {
+    let report = Report::from_analysis(analysis);
+
+    let content = serde_json::to_string_pretty(&report)
+        .map_err(|e| AnalysisError::Other(e.to_string()))?;
+
+    std::fs::write(path, content)
+        .map_err(|e| AnalysisError::Other(format!("Failed to write {}: {}", path, e)))
+}
+
+pub fn load_report(path: &str) -> Result<AnalyisisResult, AnalysisError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AnalysisError::InvalidReport(format!("Failed to read {}: {}", path, e)))?;
+
+    // Check the version first so that a newer report gives a meaningful error instead of a parse error
+    #[derive(Deserialize)]
+    struct VersionOnly {
+        format_version: u32,
+    }
+
+    let version = serde_json::from_str::<VersionOnly>(&content)
+        .map_err(|e| AnalysisError::InvalidReport(format!("{} is not a report: {}", path, e)))?
+        .format_version;
+
+    if version > REPORT_FORMAT_VERSION {
+        return Err(AnalysisError::InvalidReport(format!(
+            "{} has format version {}, only versions up to {} are supported",
+            path,
+            version,
+            REPORT_FORMAT_VERSION
+        )));
+    }
+
+    let report = serde_json::from_str::<Report>(&content)
+        .map_err(|e| AnalysisError::InvalidReport(format!("Failed to parse {}: {}", path, e)))?;
+
+    Ok(report.into_analysis(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    // Report file in the temporary directory, removed when dropped
+    struct TempReport(PathBuf);
+
+    impl TempReport {
+        fn new(name: &str) -> Self {
+            TempReport(std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id())))
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for TempReport {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn analysis(summary: Summary) -> AnalyisisResult {
+        AnalyisisResult {
+            selected_path: "/build".to_string(),
+            resolved_cmake_files_path: "/build/CMakeFiles".to_string(),
+            build_variant: BuildVariant::default(),
+            report_path: None,
+            summary,
+            file_cache: Default::default(),
+        }
+    }
+
+    #[test]
+    fn summary_survives_a_round_trip() {
+        let mut summary = Summary {
+            total_valid_files: 3,
+            total_invalid_files: 1,
+            frontend_duration_total_us: 1500,
+            backend_duration_total_us: 700,
+            first_event_time: 10,
+            last_event_time: 2010,
+            ..Default::default()
+        };
+
+        summary.target_summaries.insert("app".to_string(), TopLevelTargetSummary { total_files: 3, total_frontend_duration_us: 1500, ..Default::default() });
+        summary.source_file_process_summaries.insert("main.cpp".to_string(), SourceFileProcessSummary { total_time_us: 2200, ..Default::default() });
+        summary.frontend_operation_summaries.insert(
+            ("ns::foo".to_string(), FrontendOperation::InstantiateFunction),
+            FrontendOperationSummary { total_time_us: 400, self_time_us: 300, num: 2 },
+        );
+
+        let mut analysis = analysis(summary);
+        analysis.build_variant = BuildVariant::Config("Release".to_string());
+
+        let report = TempReport::new("summary-report");
+        write_report(&analysis, report.path()).unwrap();
+
+        let loaded = load_report(report.path()).unwrap();
+        let summary = &loaded.summary;
+
+        assert_eq!(loaded.report_path.as_deref(), Some(report.path()));
+        assert_eq!(loaded.selected_path, "/build");
+        assert_eq!(loaded.build_variant, BuildVariant::Config("Release".to_string()));
+        assert_eq!(summary.total_valid_files, 3);
+        assert_eq!(summary.total_invalid_files, 1);
+        assert_eq!(summary.frontend_duration_total_us, 1500);
+        assert_eq!(summary.last_event_time, 2010);
+        assert_eq!(summary.target_summaries["app"].total_files, 3);
+        assert_eq!(summary.source_file_process_summaries["main.cpp"].total_time_us, 2200);
+        assert_eq!(summary.frontend_operation_summaries[&("ns::foo".to_string(), FrontendOperation::InstantiateFunction)].self_time_us, 300);
+
+        // The sorted indices are recomputed on load
+        assert_eq!(summary.target_summaries_largest_duration_indices, ["app"]);
+    }
+
+    #[test]
+    fn newer_report_is_rejected_before_parsing() {
+        let report = TempReport::new("newer-report");
+        std::fs::write(report.path(), format!(r#"{{"format_version": {}, "unknown": true}}"#, REPORT_FORMAT_VERSION + 1)).unwrap();
+
+        match load_report(report.path()) {
+            Err(AnalysisError::InvalidReport(message)) => assert!(message.contains("format version"), "{}", message),
+            _ => panic!("a newer report must be rejected"),
+        }
+    }
+
+    #[test]
+    fn other_json_is_not_a_report() {
+        let report = TempReport::new("not-a-report");
+        std::fs::write(report.path(), r#"{"traceEvents": []}"#).unwrap();
+
+        match load_report(report.path()) {
+            Err(AnalysisError::InvalidReport(message)) => assert!(message.contains("is not a report"), "{}", message),
+            _ => panic!("a trace file must not load as a report"),
+        }
+    }
+
+    #[test]
+    fn include_tree_survives_a_round_trip() {
+        let mut header = IncludeTreeNode { total_time_us: 300, num: 2, ..Default::default() };
+        header.children.insert("inner.h".to_string(), IncludeTreeNode { total_time_us: 100, num: 1, ..Default::default() });
+
+        let mut summary = Summary::default();
+        summary.include_tree.children.insert("outer.h".to_string(), header);
+        summary.include_tree.update_self_times();
+
+        let report = TempReport::new("include-tree-report");
+        write_report(&analysis(summary), report.path()).unwrap();
+
+        let loaded = load_report(report.path()).unwrap();
+        let outer = &loaded.summary.include_tree.children["outer.h"];
+
+        assert_eq!(outer.total_time_us, 300);
+        assert_eq!(outer.self_time_us, 200);
+        assert_eq!(outer.num, 2);
+        assert_eq!(outer.children["inner.h"].total_time_us, 100);
+    }
+}
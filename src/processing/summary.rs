@@ -1,4 +1,15 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::compile_commands::{CompileCommand, FlagGroup};
+use super::file_summary::FileSummary;
+use super::namespace_tree::NamespaceNode;
+use super::ninja_log::{NinjaStep, NinjaSummary};
+use super::target_graph::{CriticalPath, TargetDependencies};
+use super::template_groups::TemplateGroup;
+use super::timeline::{Parallelism, Timeline};
 
 #[derive(Default)]
 pub struct Summary {
@@ -16,6 +27,10 @@ pub struct Summary {
     pub target_summaries_last_event_indices: Vec<String>,
     pub target_summaries_largest_duration_indices: Vec<String>,
 
+    // Target dependency graph read from the build tree, and the longest chain of it
+    pub target_dependencies: TargetDependencies,
+    pub critical_path: CriticalPath,
+
     pub frontend_file_process_summaries: BTreeMap<String, FrontendFileProcessSummary>,
     pub frontend_file_largest_self_time_indices: Vec<String>,
     pub frontend_file_largest_time_indices: Vec<String>,
@@ -25,12 +40,56 @@ pub struct Summary {
     pub source_file_largest_frontend_time_indices: Vec<String>,
     pub source_file_largest_backend_time_indices: Vec<String>,
 
+    // Entries of `compile_commands.json` keyed like `source_file_process_summaries`, and the
+    // translation units grouped by their flags
+    pub compile_commands: BTreeMap<String, CompileCommand>,
+    pub flag_groups: Vec<FlagGroup>,
+
     pub frontend_operation_summaries: FrontendOperationSummaries,
     pub frontend_operation_largest_total_time_indices: Vec<FrontendOperationKey>,
     pub frontend_operation_largest_self_time_indices: Vec<FrontendOperationKey>,
 
+    // Frontend operations grouped by their name without template arguments
+    pub template_groups: BTreeMap<FrontendOperationKey, TemplateGroup>,
+    pub template_group_largest_total_time_indices: Vec<FrontendOperationKey>,
+    pub template_group_largest_self_time_indices: Vec<FrontendOperationKey>,
+
     pub backend_operation_summaries: BTreeMap<String, BackendOperationSummaries>,
     pub backend_operation_largest_total_time_indices: Vec<String>,
+
+    // Frontend operations and backend symbols summed by their enclosing namespaces and classes
+    pub namespace_tree: NamespaceNode,
+
+    // LLVM passes of the backend by their name
+    pub pass_summaries: BTreeMap<String, EventSummary>,
+    pub pass_largest_total_time_indices: Vec<String>,
+    pub pass_largest_self_time_indices: Vec<String>,
+
+    // Every event with a duration by its name and detail, and by its name only
+    pub event_summaries: EventSummaries,
+    pub event_largest_total_time_indices: Vec<EventKey>,
+    pub event_largest_self_time_indices: Vec<EventKey>,
+    pub event_name_summaries: BTreeMap<String, EventSummary>,
+    pub event_name_largest_total_time_indices: Vec<String>,
+    pub event_name_largest_self_time_indices: Vec<String>,
+
+    // Include hierarchy of all translation units merged together, the root stands for all of them
+    pub include_tree: IncludeTreeNode,
+
+    // Start and duration of every valid trace file, sorted by the start time
+    pub compile_jobs: Vec<CompileJob>,
+    // Lanes and concurrency of the compile jobs, recomputed with the indices
+    pub timeline: Timeline,
+    // Concurrency metrics of the timeline, with the efficiency computed for the peak concurrency
+    pub parallelism: Parallelism,
+
+    // Steps of the last ninja build, including the ones without a trace file like linking
+    pub ninja_steps: Vec<NinjaStep>,
+    pub ninja: NinjaSummary,
+
+    // Everything a single trace file contributed, keyed like `source_file_process_summaries`.
+    // Only available when the build directory was analyzed, reports do not store it.
+    pub translation_units: BTreeMap<String, Arc<FileSummary>>,
 }
 
 impl Summary {
@@ -59,7 +118,7 @@ impl Summary {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct TopLevelTargetSummary {
     pub total_files: usize,
     pub total_frontend_duration_us: u64,
@@ -78,14 +137,43 @@ impl TopLevelTargetSummary {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct FrontendFileProcessSummary {
     pub total_time_us: u64,
     pub self_time_us: u64,
     pub num: usize,
+
+    // Translation units which included this file, the most expensive first
+    pub translation_units: Vec<IncludingTranslationUnit>,
+}
+
+// Time an included file took in a single translation unit
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct IncludingTranslationUnit {
+    pub source: String,
+    pub target_name: String,
+    pub total_time_us: u64,
+    pub self_time_us: u64,
+    pub num: usize,
 }
 
-#[derive(Default)]
+// A single compilation as it was scheduled during the build
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct CompileJob {
+    pub source: String,
+    pub target_name: String,
+    // Absolute, like `Summary::first_event_time`
+    pub start_time: u128,
+    pub duration_us: u64,
+}
+
+impl CompileJob {
+    pub fn end_time(&self) -> u128 {
+        self.start_time + self.duration_us as u128
+    }
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct SourceFileProcessSummary {
     pub total_time_us: u64,
     pub total_frontend_time_us: u64,
@@ -93,7 +181,7 @@ pub struct SourceFileProcessSummary {
 }
 
 // Frontend operations which are specific to some class or function
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub enum FrontendOperation {
     CodeGenFunction,
     DebugType,
@@ -102,7 +190,20 @@ pub enum FrontendOperation {
     ParseClass,
 }
 
-#[derive(Default)]
+impl FrontendOperation {
+    // Two letter abbreviation used in the tables
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            FrontendOperation::CodeGenFunction => "CF",
+            FrontendOperation::DebugType => "DT",
+            FrontendOperation::InstantiateClass => "IC",
+            FrontendOperation::InstantiateFunction => "IF",
+            FrontendOperation::ParseClass => "PC",
+        }
+    }
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct FrontendOperationSummary {
 
     pub total_time_us: u64,
@@ -114,8 +215,70 @@ pub type FrontendOperationKey = (String, FrontendOperation);
 
 pub type FrontendOperationSummaries = BTreeMap<FrontendOperationKey, FrontendOperationSummary>;
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct BackendOperationSummaries {
     pub total_time_us: u64,
     pub num: usize,
 }
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct EventSummary {
+    pub total_time_us: u64,
+    // Total time minus the time of the events nested in it on the same thread
+    pub self_time_us: u64,
+    pub num: usize,
+}
+
+impl EventSummary {
+    pub fn add(&mut self, other: &EventSummary) {
+        self.total_time_us += other.total_time_us;
+        self.self_time_us += other.self_time_us;
+        self.num += other.num;
+    }
+}
+
+// Name and detail of the event, the detail is empty when the event has none
+pub type EventKey = (String, String);
+
+pub type EventSummaries = BTreeMap<EventKey, EventSummary>;
+
+// Node of the include hierarchy. The children are the files included directly by this one.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct IncludeTreeNode {
+    pub total_time_us: u64,
+    // Total time minus the total time of the children
+    pub self_time_us: u64,
+    pub num: usize,
+    pub children: BTreeMap<String, IncludeTreeNode>,
+}
+
+impl IncludeTreeNode {
+    // Add the times of `other` to this node and all of its children, recursively
+    pub fn merge(&mut self, other: &IncludeTreeNode) {
+        self.total_time_us += other.total_time_us;
+        self.self_time_us += other.self_time_us;
+        self.num += other.num;
+
+        for (name, child) in other.children.iter() {
+            self.children.entry(name.clone()).or_default().merge(child);
+        }
+    }
+
+    // Compute the self time of this node and all of its children from the total times
+    pub fn update_self_times(&mut self) {
+        let children_time_us: u64 = self.children.values().map(|c| c.total_time_us).sum();
+
+        self.self_time_us = self.total_time_us.saturating_sub(children_time_us);
+
+        for child in self.children.values_mut() {
+            child.update_self_times();
+        }
+    }
+
+    // Children with the largest total time first
+    pub fn sorted_children(&self) -> Vec<(&String, &IncludeTreeNode)> {
+        let mut children: Vec<_> = self.children.iter().collect();
+        children.sort_by_key(|(_, c)| std::cmp::Reverse(c.total_time_us));
+        children
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::Deserialize;
+use tracing::{trace, warn};
+use walkdir::WalkDir;
+
+use super::summary::Summary;
+
+// Targets every target depends on, keyed by the target name as used in `Summary::target_summaries`
+pub type TargetDependencies = BTreeMap<String, BTreeSet<String>>;
+
+// Read the target dependency graph CMake wrote into the build tree.
+//
+// Two sources are merged:
+// - `<target>.dir/DependInfo.cmake`, whose `CMAKE_TARGET_LINKED_INFO_FILES` lists the `DependInfo.cmake`
+//   files of the linked targets (Makefile generators)
+// - `.cmake/api/v1/reply/target-*.json` of the CMake file API, when a query for the codemodel was made
+pub fn read_target_dependencies(cmake_files_path: &str) -> TargetDependencies {
+    let mut dependencies = TargetDependencies::new();
+
+    let cmake_files_path = Path::new(cmake_files_path);
+
+    // The build directory contains the `CMakeFiles` of all subdirectories too
+    let build_path = cmake_files_path.parent().unwrap_or(cmake_files_path);
+
+    for entry in WalkDir::new(build_path).into_iter().filter_map(|e| e.ok()) {
+        if entry.file_name() != "DependInfo.cmake" {
+            continue;
+        }
+
+        let Some(target_name) = target_name_of_dir(entry.path().parent()) else {
+            continue;
+        };
+
+        let content = match std::fs::read_to_string(entry.path()) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Failed to read {}: {}", entry.path().display(), e);
+                continue;
+            }
+        };
+
+        let linked = dependencies.entry(target_name).or_default();
+
+        for linked_info_file in linked_info_files(&content) {
+            if let Some(linked_target) = target_name_of_dir(Path::new(linked_info_file).parent()) {
+                linked.insert(linked_target);
+            }
+        }
+    }
+
+    read_file_api_dependencies(&build_path.join(".cmake/api/v1/reply"), &mut dependencies);
+
+    trace!("Read dependencies of {} targets", dependencies.len());
+
+    dependencies
+}
+
+// `CMakeFiles/foo.dir` -> `foo`
+fn target_name_of_dir(dir: Option<&Path>) -> Option<String> {
+    let name = dir?.file_name()?.to_str()?;
+    name.strip_suffix(".dir").map(|name| name.to_string())
+}
+
+// Quoted paths in the `set(CMAKE_TARGET_LINKED_INFO_FILES ...)` block
+fn linked_info_files(content: &str) -> Vec<&str> {
+    let Some(start) = content.find("set(CMAKE_TARGET_LINKED_INFO_FILES") else {
+        return Vec::new();
+    };
+
+    let block = &content[start..];
+    let block = &block[..block.find(')').unwrap_or(block.len())];
+
+    block
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct FileApiTarget {
+    name: String,
+    #[serde(default)]
+    dependencies: Vec<FileApiDependency>,
+}
+
+#[derive(Deserialize)]
+struct FileApiDependency {
+    id: String,
+}
+
+fn read_file_api_dependencies(reply_path: &Path, dependencies: &mut TargetDependencies) {
+    let Ok(entries) = std::fs::read_dir(reply_path) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+
+        if !file_name.starts_with("target-") || !file_name.ends_with(".json") {
+            continue;
+        }
+
+        let target: FileApiTarget = match std::fs::read_to_string(entry.path())
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(target) => target,
+            Err(e) => {
+                warn!("Failed to read {}: {}", entry.path().display(), e);
+                continue;
+            }
+        };
+
+        // Ids look like `foo::@6890427a1f51a3e7e1df`
+        let depends_on = target.dependencies
+            .iter()
+            .map(|dependency| dependency.id.split("::@").next().unwrap_or(&dependency.id).to_string());
+
+        dependencies.entry(target.name).or_default().extend(depends_on);
+    }
+}
+
+// Target on the critical path with the time it adds to the path
+#[derive(Clone)]
+pub struct CriticalPathStep {
+    pub target_name: String,
+    pub duration_us: u64,
+}
+
+// Chain of dependent targets with the largest sum of target durations, which bounds the build
+// time no matter how many cores are used. The duration of a target is the time from its first
+// to its last traced event.
+#[derive(Default, Clone)]
+pub struct CriticalPath {
+    // Dependencies first, the last step is the target which finishes the chain
+    pub steps: Vec<CriticalPathStep>,
+    pub duration_us: u64,
+    // Indices into `steps`, largest contribution first
+    pub largest_contribution_indices: Vec<usize>,
+}
+
+pub fn compute_critical_path(summary: &Summary) -> CriticalPath {
+    let target_duration = |name: &str| {
+        summary.target_summaries
+            .get(name)
+            .map(|target| (target.last_event_time - target.first_event_time) as u64)
+            .unwrap_or_default()
+    };
+
+    // Targets without known dependencies are still chains of their own
+    let targets: BTreeSet<&str> = summary.target_summaries
+        .keys()
+        .chain(summary.target_dependencies.keys())
+        .map(|name| name.as_str())
+        .collect();
+
+    // Length of the longest chain ending with the target, and the previous target of that chain
+    let mut longest: BTreeMap<&str, (u64, Option<&str>)> = BTreeMap::new();
+
+    fn visit<'a>(
+        target: &'a str,
+        summary: &'a Summary,
+        target_duration: &dyn Fn(&str) -> u64,
+        longest: &mut BTreeMap<&'a str, (u64, Option<&'a str>)>,
+        in_progress: &mut BTreeSet<&'a str>,
+    ) -> u64 {
+        if let Some((length, _)) = longest.get(target) {
+            return *length;
+        }
+
+        in_progress.insert(target);
+
+        let mut best: (u64, Option<&str>) = (0, None);
+
+        for dependency in summary.target_dependencies.get(target).into_iter().flatten() {
+            // Dependency cycles are not valid in CMake, but do not recurse forever on a broken graph
+            if in_progress.contains(dependency.as_str()) {
+                continue;
+            }
+
+            let length = visit(dependency, summary, target_duration, longest, in_progress);
+
+            if best.1.is_none() || length > best.0 {
+                best = (length, Some(dependency.as_str()));
+            }
+        }
+
+        in_progress.remove(target);
+
+        let length = best.0 + target_duration(target);
+        longest.insert(target, (length, best.1));
+
+        length
+    }
+
+    let mut in_progress = BTreeSet::new();
+
+    for target in targets.iter() {
+        visit(target, summary, &target_duration, &mut longest, &mut in_progress);
+    }
+
+    let Some((last_target, (duration_us, _))) = longest
+        .iter()
+        .max_by_key(|(name, (length, _))| (*length, std::cmp::Reverse(**name)))
+    else {
+        return CriticalPath::default();
+    };
+
+    let mut steps = Vec::new();
+    let mut current = Some(*last_target);
+
+    while let Some(target) = current {
+        steps.push(CriticalPathStep {
+            target_name: target.to_string(),
+            duration_us: target_duration(target),
+        });
+        current = longest[target].1;
+    }
+
+    steps.reverse();
+
+    let mut largest_contribution_indices: Vec<usize> = (0..steps.len()).collect();
+    largest_contribution_indices.sort_by_key(|i| std::cmp::Reverse(steps[*i].duration_us));
+
+    CriticalPath {
+        steps,
+        duration_us: *duration_us,
+        largest_contribution_indices,
+    }
+}
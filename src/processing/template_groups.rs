@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use super::summary::{FrontendOperationKey, Summary};
+
+// Frontend operations of the same template, whatever its template arguments are
+#[derive(Default, Clone)]
+pub struct TemplateGroup {
+    pub total_time_us: u64,
+    pub self_time_us: u64,
+    pub num: usize,
+    // Concrete names of the operations, the most expensive first
+    pub instantiations: Vec<String>,
+}
+
+// Name with the template arguments removed, `std::vector<Foo>::push_back` -> `std::vector<>::push_back`.
+// The angle brackets of `operator<`, `operator<<`, `operator->` and the like are kept, and the ones inside
+// parentheses of the template arguments like `Foo<(N > 1)>` or `Foo<decltype(a->b)>` are not brackets.
+pub fn template_name(name: &str) -> String {
+    const OPERATORS: [&str; 11] = ["<=>", "<<=", "<<", "<=", "<", "->*", "->", ">>=", ">>", ">=", ">"];
+
+    let mut result = String::with_capacity(name.len());
+    let mut depth = 0usize;
+    // Parentheses opened inside of the template arguments
+    let mut paren_depth = 0usize;
+    let mut rest = name;
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = rest.chars().next() {
+        let starts_identifier = !previous.is_some_and(|p| p.is_alphanumeric() || p == '_');
+
+        if let Some(after_operator) = rest.strip_prefix("operator").filter(|_| starts_identifier) {
+            let operator = OPERATORS.iter().find(|op| after_operator.starts_with(*op)).copied().unwrap_or("");
+
+            if depth == 0 {
+                result.push_str("operator");
+                result.push_str(operator);
+            }
+
+            rest = &after_operator[operator.len()..];
+            previous = operator.chars().last().or(Some('r'));
+            continue;
+        }
+
+        match c {
+            '(' if depth > 0 => paren_depth += 1,
+            ')' if depth > 0 => paren_depth = paren_depth.saturating_sub(1),
+            _ if paren_depth > 0 => {}
+            '<' => {
+                if depth == 0 {
+                    result.push('<');
+                }
+                depth += 1;
+            }
+            '>' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    result.push('>');
+                }
+            }
+            c if depth == 0 => result.push(c),
+            _ => {}
+        }
+
+        rest = &rest[c.len_utf8()..];
+        previous = Some(c);
+    }
+
+    result
+}
+
+// Group the frontend operations by their template name and type
+pub fn compute_template_groups(summary: &Summary) -> BTreeMap<FrontendOperationKey, TemplateGroup> {
+    let mut groups: BTreeMap<FrontendOperationKey, TemplateGroup> = BTreeMap::new();
+
+    for ((name, operation), operation_summary) in summary.frontend_operation_summaries.iter() {
+        let group = groups.entry((template_name(name), *operation)).or_default();
+
+        group.total_time_us += operation_summary.total_time_us;
+        group.self_time_us += operation_summary.self_time_us;
+        group.num += operation_summary.num;
+        group.instantiations.push(name.clone());
+    }
+
+    for ((_, operation), group) in groups.iter_mut() {
+        let time_us = |name: &String| summary.frontend_operation_summaries[&(name.clone(), *operation)].total_time_us;
+
+        group.instantiations.sort_by_cached_key(|name| std::cmp::Reverse(time_us(name)));
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::template_name;
+
+    #[test]
+    fn removes_template_arguments() {
+        assert_eq!(template_name("std::vector<Foo>::push_back"), "std::vector<>::push_back");
+        assert_eq!(template_name("std::map<int, std::vector<int>>::find"), "std::map<>::find");
+        assert_eq!(template_name("ns::foo"), "ns::foo");
+    }
+
+    #[test]
+    fn keeps_operators() {
+        assert_eq!(template_name("ns::operator<"), "ns::operator<");
+        assert_eq!(template_name("ns::operator<<"), "ns::operator<<");
+        assert_eq!(template_name("ns::operator<=>"), "ns::operator<=>");
+        assert_eq!(template_name("Foo<int>::operator->"), "Foo<>::operator->");
+        assert_eq!(template_name("Foo<int>::operator()"), "Foo<>::operator()");
+        assert_eq!(template_name("ns::operator<<<Foo>"), "ns::operator<<<>");
+        assert_eq!(template_name("ns::my_operator<int>"), "ns::my_operator<>");
+    }
+
+    #[test]
+    fn ignores_brackets_in_parentheses() {
+        assert_eq!(template_name("Foo<(N > 1)>::bar"), "Foo<>::bar");
+        assert_eq!(template_name("Foo<decltype(a->b)>::bar"), "Foo<>::bar");
+        assert_eq!(template_name("Foo<Bar<(1 < 2)>>::bar"), "Foo<>::bar");
+    }
+}
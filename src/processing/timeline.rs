@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use serde::Serialize;
+
+use super::summary::Summary;
+
+// Compile jobs packed on lanes the way a scheduler would run them, and the number of jobs
+// running over time. All times are in microseconds relative to `Summary::first_event_time`.
+#[derive(Default, Clone)]
+pub struct Timeline {
+    // Lane of every compile job, in the order of `Summary::compile_jobs`
+    pub lanes: Vec<usize>,
+    pub lane_count: usize,
+    // Number of running jobs from the given time until the next entry
+    pub concurrency: Vec<(u64, usize)>,
+    pub duration_us: u64,
+}
+
+impl Timeline {
+    pub fn peak_concurrency(&self) -> usize {
+        self.concurrency.iter().map(|(_, count)| *count).max().unwrap_or_default()
+    }
+}
+
+pub fn compute_timeline(summary: &Summary) -> Timeline {
+    let relative = |time: u128| time.saturating_sub(summary.first_event_time) as u64;
+
+    let mut timeline = Timeline {
+        lanes: Vec::with_capacity(summary.compile_jobs.len()),
+        ..Default::default()
+    };
+
+    // Jobs are sorted by the start time, so every job goes to the lowest lane which is free at its start
+    let mut busy_lanes: BinaryHeap<Reverse<(u128, usize)>> = BinaryHeap::new();
+    let mut free_lanes: BinaryHeap<Reverse<usize>> = BinaryHeap::new();
+
+    for job in summary.compile_jobs.iter() {
+        while let Some(Reverse((end_time, lane))) = busy_lanes.peek().copied() {
+            if end_time > job.start_time {
+                break;
+            }
+            busy_lanes.pop();
+            free_lanes.push(Reverse(lane));
+        }
+
+        let lane = match free_lanes.pop() {
+            Some(Reverse(lane)) => lane,
+            None => {
+                timeline.lane_count += 1;
+                timeline.lane_count - 1
+            }
+        };
+
+        busy_lanes.push(Reverse((job.end_time(), lane)));
+        timeline.lanes.push(lane);
+
+        timeline.duration_us = timeline.duration_us.max(relative(job.end_time()));
+    }
+
+    // Ends sort before starts at the same time, so back to back jobs do not count as concurrent
+    let mut changes: Vec<(u64, i64)> = summary.compile_jobs
+        .iter()
+        .flat_map(|job| [(relative(job.start_time), 1), (relative(job.end_time()), -1)])
+        .collect();
+
+    changes.sort();
+
+    let mut running: i64 = 0;
+
+    for (time, change) in changes {
+        running += change;
+
+        match timeline.concurrency.last_mut() {
+            Some(last) if last.0 == time => last.1 = running as usize,
+            _ => timeline.concurrency.push((time, running as usize)),
+        }
+    }
+
+    timeline
+}
+
+// Number of sequential stretches kept in the `Parallelism`
+const SEQUENTIAL_STRETCH_LIMIT: usize = 10;
+
+// Period in which at most one compile job was running, relative to `Summary::first_event_time`
+#[derive(Serialize, Clone)]
+pub struct SequentialStretch {
+    pub start_us: u64,
+    pub duration_us: u64,
+}
+
+// How well the compile jobs used the available cores
+#[derive(Serialize, Default, Clone)]
+pub struct Parallelism {
+    // Sum of the durations of all compile jobs
+    pub cpu_time_us: u64,
+    // From the start of the first compile job to the end of the last one
+    pub wall_time_us: u64,
+    pub average_concurrency: f64,
+    pub peak_concurrency: usize,
+    // Core count the efficiency is computed for, the peak concurrency unless given explicitly
+    pub cores: usize,
+    pub parallel_efficiency: f64,
+    // Longest first
+    pub sequential_stretches: Vec<SequentialStretch>,
+}
+
+pub fn compute_parallelism(summary: &Summary, cores: Option<usize>) -> Parallelism {
+    let timeline = &summary.timeline;
+
+    let (Some((first_time, _)), Some((last_time, _))) = (timeline.concurrency.first(), timeline.concurrency.last()) else {
+        return Parallelism::default();
+    };
+
+    let cpu_time_us = summary.compile_jobs.iter().map(|job| job.duration_us).sum();
+    let wall_time_us = last_time - first_time;
+    let peak_concurrency = timeline.peak_concurrency();
+    let cores = cores.unwrap_or(peak_concurrency).max(1);
+
+    let average_concurrency = cpu_time_us as f64 / wall_time_us.max(1) as f64;
+
+    // Merge the neighbouring steps with at most one running job
+    let mut sequential_stretches: Vec<SequentialStretch> = Vec::new();
+    let mut stretch_start = None;
+
+    for (time, count) in timeline.concurrency.iter() {
+        match (stretch_start, *count <= 1) {
+            (None, true) => stretch_start = Some(*time),
+            (Some(start), false) => {
+                sequential_stretches.push(SequentialStretch { start_us: start, duration_us: time - start });
+                stretch_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    // The last step always has no running jobs, the stretch ends with the build
+    if let Some(start) = stretch_start {
+        sequential_stretches.push(SequentialStretch { start_us: start, duration_us: last_time - start });
+    }
+
+    sequential_stretches.retain(|stretch| stretch.duration_us > 0);
+    sequential_stretches.sort_by_key(|stretch| std::cmp::Reverse(stretch.duration_us));
+    sequential_stretches.truncate(SEQUENTIAL_STRETCH_LIMIT);
+
+    Parallelism {
+        cpu_time_us,
+        wall_time_us,
+        average_concurrency,
+        peak_concurrency,
+        cores,
+        parallel_efficiency: average_concurrency / cores as f64,
+        sequential_stretches,
+    }
+}
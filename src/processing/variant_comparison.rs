@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::model::BuildVariant;
+
+use super::summary::{FrontendOperationKey, Summary};
+
+// Time of a single item in every analyzed configuration, in the order of `VariantComparison::build_variants`.
+// A missing value means that the item does not exist in that configuration.
+pub struct VariantEntry<K> {
+    pub key: K,
+    pub times_us: Vec<Option<u64>>,
+}
+
+impl<K> VariantEntry<K> {
+    // Time of the configuration at `index` relative to the first configuration
+    pub fn ratio(&self, index: usize) -> Option<f64> {
+        match (self.times_us.first().copied().flatten(), self.times_us.get(index).copied().flatten()) {
+            (Some(first_us), Some(us)) if first_us > 0 => Some(us as f64 / first_us as f64),
+            _ => None,
+        }
+    }
+
+    // Ratio of the last configuration to the first one, used for the ordering
+    pub fn last_ratio(&self) -> Option<f64> {
+        self.ratio(self.times_us.len().saturating_sub(1))
+    }
+
+    pub fn max_us(&self) -> u64 {
+        self.times_us.iter().flatten().copied().max().unwrap_or_default()
+    }
+}
+
+// Entries sorted by the largest time in any configuration
+pub struct VariantTable<K> {
+    pub entries: Vec<VariantEntry<K>>,
+    // Largest ratio of the last configuration to the first one first, entries without a ratio last
+    pub largest_ratio_indices: Vec<usize>,
+}
+
+impl<K> VariantTable<K> {
+    // Iterate by the largest time, or by the largest ratio
+    pub fn ordered(&self, by_ratio: bool) -> Box<dyn Iterator<Item = &VariantEntry<K>> + '_> {
+        if by_ratio {
+            Box::new(self.largest_ratio_indices.iter().map(|&i| &self.entries[i]))
+        } else {
+            Box::new(self.entries.iter())
+        }
+    }
+}
+
+// Totals of a single configuration
+pub struct VariantTotals {
+    pub total_files: usize,
+    pub frontend_sec: f64,
+    pub backend_sec: f64,
+    pub inferred_used_time_sec: f64,
+}
+
+// Side by side view of the same build directory analyzed for several configurations
+pub struct VariantComparison {
+    pub build_variants: Vec<BuildVariant>,
+    pub totals: Vec<VariantTotals>,
+    pub targets: VariantTable<String>,
+    pub includes: VariantTable<String>,
+    pub sources: VariantTable<String>,
+    pub frontend_operations: VariantTable<FrontendOperationKey>,
+    pub backend_operations: VariantTable<String>,
+}
+
+fn join_maps<K: Ord + Clone, V>(
+    maps: &[&BTreeMap<K, V>],
+    time_us: impl Fn(&V) -> u64,
+) -> VariantTable<K> {
+    let keys: BTreeSet<&K> = maps.iter().flat_map(|map| map.keys()).collect();
+
+    let mut entries: Vec<VariantEntry<K>> = keys
+        .into_iter()
+        .map(|key| VariantEntry {
+            key: key.clone(),
+            times_us: maps.iter().map(|map| map.get(key).map(&time_us)).collect(),
+        })
+        .collect();
+
+    // Stable so that equal times stay in key order
+    entries.sort_by_key(|e| std::cmp::Reverse(e.max_us()));
+
+    let mut largest_ratio_indices: Vec<usize> = (0..entries.len()).collect();
+
+    largest_ratio_indices.sort_by(|&a, &b| {
+        let ratio = |i: usize| entries[i].last_ratio().unwrap_or(f64::NEG_INFINITY);
+        ratio(b).total_cmp(&ratio(a))
+    });
+
+    VariantTable {
+        entries,
+        largest_ratio_indices,
+    }
+}
+
+// Join the tables of the summaries of every configuration, the first one is the reference of the ratios
+pub fn compare_variants(analyses: &[(BuildVariant, &Summary)]) -> VariantComparison {
+    let summaries: Vec<&Summary> = analyses.iter().map(|(_, summary)| *summary).collect();
+
+    VariantComparison {
+        build_variants: analyses.iter().map(|(build_variant, _)| build_variant.clone()).collect(),
+        totals: summaries
+            .iter()
+            .map(|summary| VariantTotals {
+                total_files: summary.total_files(),
+                frontend_sec: summary.frontend_duration_sec(),
+                backend_sec: summary.backend_duration_sec(),
+                inferred_used_time_sec: summary.inferred_used_time_secs(),
+            })
+            .collect(),
+        targets: join_maps(
+            &summaries.iter().map(|s| &s.target_summaries).collect::<Vec<_>>(),
+            |s| s.total_frontend_duration_us + s.total_backend_duration_us,
+        ),
+        includes: join_maps(
+            &summaries.iter().map(|s| &s.frontend_file_process_summaries).collect::<Vec<_>>(),
+            |s| s.total_time_us,
+        ),
+        sources: join_maps(
+            &summaries.iter().map(|s| &s.source_file_process_summaries).collect::<Vec<_>>(),
+            |s| s.total_time_us,
+        ),
+        frontend_operations: join_maps(
+            &summaries.iter().map(|s| &s.frontend_operation_summaries).collect::<Vec<_>>(),
+            |s| s.total_time_us,
+        ),
+        backend_operations: join_maps(
+            &summaries.iter().map(|s| &s.backend_operation_summaries).collect::<Vec<_>>(),
+            |s| s.total_time_us,
+        ),
+    }
+}
+
+// Configurations of a multi config build directory which have trace files, in alphabetical order
+pub fn configurations(selected_path: &str) -> Vec<BuildVariant> {
+    super::discover_build_variants(selected_path)
+        .into_iter()
+        .map(|discovered| discovered.build_variant)
+        .filter(|build_variant| *build_variant != BuildVariant::SingleConfig)
+        .collect()
+}